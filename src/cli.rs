@@ -1,28 +1,137 @@
-use clap::Parser;
+use std::path::PathBuf;
 
-use crate::{ExecutionArgs, MyResult};
+use clap::{Parser, Subcommand};
+
+use crate::{Command, ExecutionArgs, MyResult, ToolArgs};
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct CliArgs {
-    #[arg(value_parser = clap::value_parser!(u16).range(2015..=2025))]
-    pub year: u16,
+    #[command(subcommand)]
+    pub command: Option<CliCommand>,
+
+    #[arg(required = true, value_parser = clap::value_parser!(u16).range(2015..=2025))]
+    pub year: Option<u16>,
+
+    #[arg(required = true, value_parser = clap::value_parser!(u8).range(1..=25))]
+    pub day: Option<u8>,
+
+    #[arg(required = true, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: Option<u8>,
+
+    #[arg(
+        short,
+        long,
+        help = "Print solver diagnostics such as cache statistics to stderr"
+    )]
+    pub verbose: bool,
+}
+
+#[derive(Subcommand)]
+enum CliCommand {
+    #[command(about = "Helpers for inspecting puzzle inputs")]
+    Tool {
+        #[command(subcommand)]
+        tool: CliTool,
+    },
+}
+
+#[derive(Subcommand)]
+enum CliTool {
+    #[command(about = "Disassemble a 2024 day 17 chronospatial computer program")]
+    ChronoDisasm {
+        input: PathBuf,
+
+        #[arg(long, help = "Run the program and log pc and registers for every step")]
+        trace: bool,
 
-    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
-    pub day: u8,
+        #[arg(
+            long = "break",
+            value_name = "PC",
+            help = "Pause before the instruction at PC and log the registers"
+        )]
+        breakpoints: Vec<usize>,
+    },
 
-    #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
-    pub part: u8,
+    #[command(about = "Score 2023 day 07 camel cards with a custom set of wildcards")]
+    CamelCards {
+        input: PathBuf,
+
+        #[arg(
+            long,
+            value_name = "CARDS",
+            default_value = "",
+            help = "Card labels that act as wildcards, e.g. J"
+        )]
+        wildcards: String,
+    },
+
+    #[command(about = "Show the 2022 day 05 crate stacks after every move")]
+    SupplyStacks {
+        input: PathBuf,
+
+        #[arg(
+            long,
+            default_value_t = 9000,
+            help = "CrateMover model, 9000 moves one crate at a time and 9001 moves them at once"
+        )]
+        model: u32,
+    },
+
+    #[command(about = "Show the 2022 day 07 directory tree with the size of every entry")]
+    Filesystem {
+        input: PathBuf,
+
+        #[arg(
+            long,
+            default_value = "/",
+            help = "Absolute path of the directory or file to show"
+        )]
+        path: String,
+    },
+
+    #[command(about = "Draw the 2022 day 09 rope and the positions visited by its tail")]
+    RopeBridge {
+        input: PathBuf,
+
+        #[arg(long, default_value_t = 2, help = "Number of knots including the head")]
+        knots: usize,
+    },
 }
 
-pub fn parse_args() -> MyResult<ExecutionArgs> {
+pub fn parse_args() -> MyResult<Command> {
     let args = CliArgs::try_parse()?;
 
+    if let Some(CliCommand::Tool { tool }) = args.command {
+        let res = match tool {
+            CliTool::ChronoDisasm {
+                input,
+                trace,
+                breakpoints,
+            } => ToolArgs::ChronoDisasm {
+                input,
+                trace,
+                breakpoints,
+            },
+            CliTool::CamelCards { input, wildcards } => ToolArgs::CamelCards { input, wildcards },
+            CliTool::SupplyStacks { input, model } => ToolArgs::SupplyStacks { input, model },
+            CliTool::Filesystem { input, path } => ToolArgs::Filesystem { input, path },
+            CliTool::RopeBridge { input, knots } => ToolArgs::RopeBridge { input, knots },
+        };
+        return Ok(Command::Tool(res));
+    }
+
+    let (Some(year), Some(day), Some(part)) = (args.year, args.day, args.part) else {
+        return Err("Year, day and part are required".into());
+    };
+
     let res = ExecutionArgs {
-        year: args.year,
-        day: args.day,
-        part: args.part,
+        year,
+        day,
+        part,
+        verbose: args.verbose,
     };
 
-    Ok(res)
+    Ok(Command::Solve(res))
 }
@@ -0,0 +1,197 @@
+use std::{collections::HashMap, hash::Hash};
+
+// All detectors assume the sequence of states eventually repeats, otherwise they never return.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub prefix_length: usize,
+    pub cycle_length: usize,
+}
+
+impl Cycle {
+    pub fn reduce_steps(&self, steps: usize) -> usize {
+        if steps < self.prefix_length {
+            return steps;
+        }
+
+        self.prefix_length + (steps - self.prefix_length) % self.cycle_length
+    }
+}
+
+pub fn floyd<S, F>(start: &S, step: F) -> Cycle
+where
+    S: Clone + Eq,
+    F: Fn(&S) -> S,
+{
+    let mut tortoise = step(start);
+    let mut hare = step(&step(start));
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&step(&hare));
+    }
+
+    let mut prefix_length = 0;
+    tortoise = start.clone();
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix_length += 1;
+    }
+
+    let mut cycle_length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        cycle_length += 1;
+    }
+
+    Cycle {
+        prefix_length,
+        cycle_length,
+    }
+}
+
+pub fn brent<S, F>(start: &S, step: F) -> Cycle
+where
+    S: Clone + Eq,
+    F: Fn(&S) -> S,
+{
+    let mut power = 1;
+    let mut cycle_length = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(start);
+    while tortoise != hare {
+        if power == cycle_length {
+            tortoise = hare.clone();
+            power *= 2;
+            cycle_length = 0;
+        }
+        hare = step(&hare);
+        cycle_length += 1;
+    }
+
+    tortoise = start.clone();
+    hare = start.clone();
+    for _ in 0..cycle_length {
+        hare = step(&hare);
+    }
+
+    let mut prefix_length = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix_length += 1;
+    }
+
+    Cycle {
+        prefix_length,
+        cycle_length,
+    }
+}
+
+pub fn detect_with_hash<S, F>(start: &S, step: F) -> Cycle
+where
+    S: Clone + Eq + Hash,
+    F: Fn(&S) -> S,
+{
+    let mut first_seen = HashMap::new();
+    let mut state = start.clone();
+    for idx in 0.. {
+        if let Some(&prefix_length) = first_seen.get(&state) {
+            return Cycle {
+                prefix_length,
+                cycle_length: idx - prefix_length,
+            };
+        }
+
+        let next_state = step(&state);
+        first_seen.insert(state, idx);
+        state = next_state;
+    }
+
+    unreachable!()
+}
+
+pub fn state_after<S, F>(start: &S, step: F, steps: usize) -> S
+where
+    S: Clone + Eq + Hash,
+    F: Fn(&S) -> S,
+{
+    let mut states = Vec::new();
+    let mut first_seen = HashMap::new();
+    let mut state = start.clone();
+    while states.len() < steps {
+        if let Some(&prefix_length) = first_seen.get(&state) {
+            let cycle = Cycle {
+                prefix_length,
+                cycle_length: states.len() - prefix_length,
+            };
+            return states.swap_remove(cycle.reduce_steps(steps));
+        }
+
+        let next_state = step(&state);
+        first_seen.insert(state.clone(), states.len());
+        states.push(state);
+        state = next_state;
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{brent, detect_with_hash, floyd, state_after, Cycle};
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 6 -> 3
+    fn step(state: &u32) -> u32 {
+        if *state == 6 {
+            3
+        } else {
+            state + 1
+        }
+    }
+
+    #[test]
+    fn detectors_agree() {
+        let expected = Cycle {
+            prefix_length: 3,
+            cycle_length: 4,
+        };
+
+        assert_eq!(floyd(&0, step), expected);
+        assert_eq!(brent(&0, step), expected);
+        assert_eq!(detect_with_hash(&0, step), expected);
+    }
+
+    #[test]
+    fn cycle_without_prefix() {
+        let rotate = |state: &u32| (state + 1) % 5;
+        let expected = Cycle {
+            prefix_length: 0,
+            cycle_length: 5,
+        };
+
+        assert_eq!(floyd(&2, rotate), expected);
+        assert_eq!(brent(&2, rotate), expected);
+        assert_eq!(detect_with_hash(&2, rotate), expected);
+    }
+
+    #[test]
+    fn fast_forward_using_cycle() {
+        assert_eq!(state_after(&0, step, 0), 0);
+        assert_eq!(state_after(&0, step, 2), 2);
+        assert_eq!(state_after(&0, step, 7), 3);
+        assert_eq!(state_after(&0, step, 1_000_000_000), 4);
+    }
+
+    #[test]
+    fn reduce_steps() {
+        let cycle = Cycle {
+            prefix_length: 3,
+            cycle_length: 4,
+        };
+
+        assert_eq!(cycle.reduce_steps(2), 2);
+        assert_eq!(cycle.reduce_steps(7), 3);
+        assert_eq!(cycle.reduce_steps(12), 4);
+    }
+}
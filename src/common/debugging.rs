@@ -1,6 +1,27 @@
-use std::collections::HashSet;
+use std::{
+    collections::HashSet,
+    fmt::Display,
+    sync::atomic::{AtomicBool, Ordering},
+};
 
-use super::math_2d::{Grid, Point};
+use super::math_2d::{Grid, Point, SparseGrid};
+
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+
+pub fn set_verbose(verbose: bool) {
+    VERBOSE.store(verbose, Ordering::Relaxed);
+}
+
+pub fn is_verbose() -> bool {
+    VERBOSE.load(Ordering::Relaxed)
+}
+
+// Diagnostics go to stderr so they never mix with the solution on stdout
+pub fn log_verbose(message: impl Display) {
+    if is_verbose() {
+        eprintln!("{}", message);
+    }
+}
 
 pub fn print_grid<T, F>(grid: &Grid<T>, mapper: F, points_to_highlight: HashSet<Point<i32>>)
 where
@@ -18,3 +39,62 @@ where
         println!();
     }
 }
+
+pub fn print_sparse_grid<T, F>(
+    grid: &SparseGrid<T>,
+    mapper: F,
+    points_to_highlight: HashSet<Point<i64>>,
+) where
+    F: Fn(&T) -> char,
+{
+    print!("{}", render_sparse_grid(grid, mapper, &points_to_highlight));
+}
+
+pub fn render_sparse_grid<T, F>(
+    grid: &SparseGrid<T>,
+    mapper: F,
+    points_to_highlight: &HashSet<Point<i64>>,
+) -> String
+where
+    F: Fn(&T) -> char,
+{
+    let mut output = String::new();
+    let Some(bounds) = grid.bounds() else {
+        return output;
+    };
+
+    for y in bounds.min.y..=bounds.max.y {
+        for x in bounds.min.x..=bounds.max.x {
+            let point = Point::new(x, y);
+            if points_to_highlight.contains(&point) {
+                output.push('O');
+            } else {
+                output.push(mapper(&grid[point]));
+            }
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::common::math_2d::{Point, SparseGrid};
+
+    use super::render_sparse_grid;
+
+    #[test]
+    fn render_sparse_grid_within_bounds() {
+        let mut grid = SparseGrid::new(false);
+        grid.insert(Point::new(-1, -1), true);
+        grid.insert(Point::new(1, 0), true);
+
+        let highlights = HashSet::from([Point::new(0, 0)]);
+        let output = render_sparse_grid(&grid, |v| if *v { '#' } else { '.' }, &highlights);
+
+        assert_eq!(output, "#..\n.O#\n");
+    }
+}
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+#[derive(Clone, Debug)]
+pub struct DisjointSet {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+    component_count: usize,
+}
+
+impl DisjointSet {
+    pub fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+            component_count: len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.component_count
+    }
+
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        let mut current = element;
+        while self.parents[current] != root {
+            let next = self.parents[current];
+            self.parents[current] = root;
+            current = next;
+        }
+
+        root
+    }
+
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let mut root_a = self.find(a);
+        let mut root_b = self.find(b);
+        if root_a == root_b {
+            return false;
+        }
+
+        if self.sizes[root_a] < self.sizes[root_b] {
+            std::mem::swap(&mut root_a, &mut root_b);
+        }
+
+        self.parents[root_b] = root_a;
+        self.sizes[root_a] += self.sizes[root_b];
+        self.component_count -= 1;
+
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    pub fn component_size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+
+    pub fn component_sizes(&self) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&element| self.parents[element] == element)
+            .map(|root| self.sizes[root])
+            .collect()
+    }
+
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut components: HashMap<usize, Vec<usize>> = HashMap::new();
+        for element in 0..self.len() {
+            let root = self.find(element);
+            components.entry(root).or_default().push(element);
+        }
+
+        let mut components = components.into_values().collect::<Vec<_>>();
+        components.sort_by_key(|c| c[0]);
+        components
+    }
+}
+
+pub fn kruskal<W: Ord>(node_count: usize, edges: Vec<(usize, usize, W)>) -> Vec<(usize, usize, W)> {
+    let mut edges = edges;
+    edges.sort_by(|(_, _, a), (_, _, b)| a.cmp(b));
+
+    let mut set = DisjointSet::new(node_count);
+    let mut spanning_edges = Vec::new();
+    for (a, b, weight) in edges {
+        if set.union(a, b) {
+            spanning_edges.push((a, b, weight));
+            if set.component_count() == 1 {
+                break;
+            }
+        }
+    }
+
+    spanning_edges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{kruskal, DisjointSet};
+
+    #[test]
+    fn union_merges_components() {
+        let mut set = DisjointSet::new(6);
+        assert_eq!(set.component_count(), 6);
+
+        assert!(set.union(0, 1));
+        assert!(set.union(2, 3));
+        assert!(set.union(1, 3));
+        assert!(!set.union(0, 2));
+
+        assert!(set.connected(0, 3));
+        assert!(!set.connected(0, 4));
+        assert_eq!(set.component_count(), 3);
+        assert_eq!(set.component_size(2), 4);
+        assert_eq!(set.component_size(5), 1);
+    }
+
+    #[test]
+    fn enumerate_components() {
+        let mut set = DisjointSet::new(5);
+        set.union(4, 1);
+        set.union(3, 0);
+
+        assert_eq!(set.components(), [vec![0, 3], vec![1, 4], vec![2]]);
+
+        let mut sizes = set.component_sizes();
+        sizes.sort();
+        assert_eq!(sizes, [1, 2, 2]);
+    }
+
+    #[test]
+    fn kruskal_builds_minimum_spanning_forest() {
+        let edges = vec![
+            (0, 1, 4),
+            (1, 2, 1),
+            (0, 2, 3),
+            (2, 3, 2),
+            (1, 3, 5),
+            (4, 5, 7),
+        ];
+
+        let spanning_edges = kruskal(6, edges);
+        assert_eq!(spanning_edges, [(1, 2, 1), (2, 3, 2), (0, 2, 3), (4, 5, 7)]);
+    }
+}
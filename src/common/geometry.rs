@@ -0,0 +1,249 @@
+use anyhow::{bail, Result};
+
+use crate::common::{math_2d::Point, number_theory::gcd};
+
+pub fn twice_polygon_area(vertices: &[Point<i64>]) -> u64 {
+    let signed_area: i128 = vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(a, b)| i128::from(a.x) * i128::from(b.y) - i128::from(b.x) * i128::from(a.y))
+        .sum();
+
+    signed_area.unsigned_abs() as u64
+}
+
+pub fn boundary_lattice_points(vertices: &[Point<i64>]) -> u64 {
+    vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(a, b)| gcd(a.x.abs_diff(b.x), a.y.abs_diff(b.y)))
+        .sum()
+}
+
+// Pick's theorem: A = I + B/2 - 1
+pub fn interior_lattice_points(vertices: &[Point<i64>]) -> u64 {
+    let twice_area = twice_polygon_area(vertices);
+    let boundary = boundary_lattice_points(vertices);
+
+    (twice_area + 2).saturating_sub(boundary) / 2
+}
+
+// Polygon with axis aligned edges whose vertices are the centers of lattice tiles. Tiles on
+// the outline count as part of the polygon.
+pub struct RectilinearPolygon {
+    xs: Vec<i64>,
+    ys: Vec<i64>,
+    outside_prefix_sums: Vec<Vec<u32>>,
+}
+
+impl RectilinearPolygon {
+    pub fn new(vertices: &[Point<i64>]) -> Result<Self> {
+        if vertices.len() < 4 {
+            bail!(
+                "A rectilinear polygon needs at least 4 vertices, found {}",
+                vertices.len()
+            );
+        }
+
+        let edges = vertices
+            .iter()
+            .zip(vertices.iter().cycle().skip(1))
+            .map(|(&a, &b)| (a, b))
+            .collect::<Vec<_>>();
+        for (idx, (a, b)) in edges.iter().enumerate() {
+            if a == b || (a.x != b.x && a.y != b.y) {
+                bail!(
+                    "Edge {} from ({}, {}) to ({}, {}) is not axis aligned",
+                    idx + 1,
+                    a.x,
+                    a.y,
+                    b.x,
+                    b.y
+                );
+            }
+        }
+
+        // Every band between two consecutive compressed coordinates is either fully inside
+        // or fully outside of the polygon.
+        let compress = |values: Vec<i64>| {
+            let mut compressed = values
+                .into_iter()
+                .flat_map(|value| [value, value + 1])
+                .collect::<Vec<_>>();
+            compressed.sort_unstable();
+            compressed.dedup();
+            compressed
+        };
+        let xs = compress(vertices.iter().map(|v| v.x).collect());
+        let ys = compress(vertices.iter().map(|v| v.y).collect());
+        let band = |values: &[i64], value: i64| values.partition_point(|&v| v <= value) - 1;
+
+        let mut inside = vec![vec![false; xs.len()]; ys.len()];
+        for (a, b) in edges.iter() {
+            for row in inside
+                .iter_mut()
+                .take(band(&ys, a.y.max(b.y)) + 1)
+                .skip(band(&ys, a.y.min(b.y)))
+            {
+                for cell in row
+                    .iter_mut()
+                    .take(band(&xs, a.x.max(b.x)) + 1)
+                    .skip(band(&xs, a.x.min(b.x)))
+                {
+                    *cell = true;
+                }
+            }
+        }
+
+        for (row, &y) in inside.iter_mut().zip(&ys) {
+            let mut crossings = edges
+                .iter()
+                .filter(|(a, b)| a.x == b.x && a.y.min(b.y) <= y && y < a.y.max(b.y))
+                .map(|(a, _)| a.x)
+                .collect::<Vec<_>>();
+            crossings.sort_unstable();
+
+            let mut crossing_idx = 0;
+            for (cell, &x) in row.iter_mut().zip(&xs) {
+                while crossing_idx < crossings.len() && crossings[crossing_idx] < x {
+                    crossing_idx += 1;
+                }
+                if crossing_idx % 2 == 1 {
+                    *cell = true;
+                }
+            }
+        }
+
+        let mut outside_prefix_sums = vec![vec![0; xs.len() + 1]; ys.len() + 1];
+        for (row_idx, row) in inside.iter().enumerate() {
+            for (col_idx, &cell) in row.iter().enumerate() {
+                outside_prefix_sums[row_idx + 1][col_idx + 1] = u32::from(!cell)
+                    + outside_prefix_sums[row_idx][col_idx + 1]
+                    + outside_prefix_sums[row_idx + 1][col_idx]
+                    - outside_prefix_sums[row_idx][col_idx];
+            }
+        }
+
+        Ok(Self {
+            xs,
+            ys,
+            outside_prefix_sums,
+        })
+    }
+
+    pub fn contains_point(&self, point: Point<i64>) -> bool {
+        self.contains_rectangle(point, point)
+    }
+
+    pub fn contains_rectangle(&self, corner: Point<i64>, opposite_corner: Point<i64>) -> bool {
+        let (Some(min_x), Some(max_x)) = (
+            self.x_band(corner.x.min(opposite_corner.x)),
+            self.x_band(corner.x.max(opposite_corner.x)),
+        ) else {
+            return false;
+        };
+        let (Some(min_y), Some(max_y)) = (
+            self.y_band(corner.y.min(opposite_corner.y)),
+            self.y_band(corner.y.max(opposite_corner.y)),
+        ) else {
+            return false;
+        };
+
+        let sums = &self.outside_prefix_sums;
+        let outside_count = sums[max_y + 1][max_x + 1] + sums[min_y][min_x]
+            - sums[min_y][max_x + 1]
+            - sums[max_y + 1][min_x];
+
+        outside_count == 0
+    }
+
+    fn x_band(&self, x: i64) -> Option<usize> {
+        Self::band(&self.xs, x)
+    }
+
+    fn y_band(&self, y: i64) -> Option<usize> {
+        Self::band(&self.ys, y)
+    }
+
+    fn band(values: &[i64], value: i64) -> Option<usize> {
+        // The last band starts after the polygon and is always outside
+        let band = values.partition_point(|&v| v <= value).checked_sub(1)?;
+        (band + 1 < values.len()).then_some(band)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::math_2d::Point;
+
+    use super::{
+        boundary_lattice_points, interior_lattice_points, twice_polygon_area, RectilinearPolygon,
+    };
+
+    fn points(coordinates: &[(i64, i64)]) -> Vec<Point<i64>> {
+        coordinates.iter().map(|&(x, y)| Point::new(x, y)).collect()
+    }
+
+    #[test]
+    fn shoelace_and_pick() {
+        let square = points(&[(0, 0), (4, 0), (4, 4), (0, 4)]);
+        assert_eq!(twice_polygon_area(&square), 32);
+        assert_eq!(boundary_lattice_points(&square), 16);
+        assert_eq!(interior_lattice_points(&square), 9);
+
+        let triangle = points(&[(0, 0), (0, 3), (3, 0)]);
+        assert_eq!(twice_polygon_area(&triangle), 9);
+        assert_eq!(boundary_lattice_points(&triangle), 9);
+        assert_eq!(interior_lattice_points(&triangle), 1);
+    }
+
+    #[test]
+    fn rectilinear_point_in_polygon() {
+        let polygon = RectilinearPolygon::new(&points(&[
+            (7, 1),
+            (11, 1),
+            (11, 7),
+            (9, 7),
+            (9, 5),
+            (2, 5),
+            (2, 3),
+            (7, 3),
+        ]))
+        .unwrap();
+
+        assert!(polygon.contains_point(Point::new(7, 1)));
+        assert!(polygon.contains_point(Point::new(8, 2)));
+        assert!(polygon.contains_point(Point::new(2, 4)));
+        assert!(polygon.contains_point(Point::new(10, 6)));
+        assert!(!polygon.contains_point(Point::new(3, 2)));
+        assert!(!polygon.contains_point(Point::new(8, 6)));
+        assert!(!polygon.contains_point(Point::new(12, 4)));
+        assert!(!polygon.contains_point(Point::new(-100, 4)));
+    }
+
+    #[test]
+    fn rectilinear_rectangle_in_polygon() {
+        let polygon = RectilinearPolygon::new(&points(&[
+            (7, 1),
+            (11, 1),
+            (11, 7),
+            (9, 7),
+            (9, 5),
+            (2, 5),
+            (2, 3),
+            (7, 3),
+        ]))
+        .unwrap();
+
+        assert!(polygon.contains_rectangle(Point::new(9, 5), Point::new(2, 3)));
+        assert!(polygon.contains_rectangle(Point::new(7, 1), Point::new(11, 5)));
+        assert!(!polygon.contains_rectangle(Point::new(2, 5), Point::new(11, 1)));
+        assert!(!polygon.contains_rectangle(Point::new(7, 3), Point::new(11, 7)));
+    }
+
+    #[test]
+    fn reject_diagonal_edges() {
+        let result = RectilinearPolygon::new(&points(&[(0, 0), (4, 0), (4, 4), (1, 3)]));
+        assert!(result.is_err());
+    }
+}
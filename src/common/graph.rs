@@ -0,0 +1,333 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use anyhow::{anyhow, bail, Result};
+use itertools::Itertools;
+
+pub type NodeId = usize;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum VisitState {
+    InProgress,
+    Done(u64),
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Graph {
+    names: Vec<String>,
+    ids: HashMap<String, NodeId>,
+    edges: Vec<Vec<NodeId>>,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn add_node(&mut self, name: &str) -> NodeId {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.edges.push(Vec::new());
+
+        id
+    }
+
+    pub fn add_edge(&mut self, from: &str, to: &str) {
+        let from = self.add_node(from);
+        let to = self.add_node(to);
+        self.connect(from, to);
+    }
+
+    pub fn connect(&mut self, from: NodeId, to: NodeId) {
+        if !self.edges[from].contains(&to) {
+            self.edges[from].push(to);
+        }
+    }
+
+    pub fn node_id(&self, name: &str) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[id]
+    }
+
+    pub fn neighbours(&self, id: NodeId) -> &[NodeId] {
+        &self.edges[id]
+    }
+
+    pub fn toposort(&self) -> Result<Vec<NodeId>> {
+        let mut in_degrees = vec![0; self.len()];
+        for &to in self.edges.iter().flatten() {
+            in_degrees[to] += 1;
+        }
+
+        let mut ready = (0..self.len())
+            .filter(|&id| in_degrees[id] == 0)
+            .collect::<VecDeque<_>>();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(id) = ready.pop_front() {
+            order.push(id);
+            for &next in self.edges[id].iter() {
+                in_degrees[next] -= 1;
+                if in_degrees[next] == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+
+        if order.len() != self.len() {
+            let remaining = (0..self.len())
+                .filter(|&id| in_degrees[id] > 0)
+                .collect::<HashSet<_>>();
+            bail!(
+                "Graph contains a cycle: {}",
+                self.describe_cycle(&remaining)
+            );
+        }
+
+        Ok(order)
+    }
+
+    pub fn reachable_from(&self, start: NodeId) -> HashSet<NodeId> {
+        let mut reachable = HashSet::new();
+        let mut to_visit = self.edges[start].clone();
+        while let Some(id) = to_visit.pop() {
+            if reachable.insert(id) {
+                to_visit.extend(self.edges[id].iter().copied());
+            }
+        }
+
+        reachable
+    }
+
+    pub fn count_paths(&self, from: &str, to: &str) -> Result<u64> {
+        let from = self.require_node(from)?;
+        let to = self.require_node(to)?;
+
+        let mut states = HashMap::new();
+        self.count_paths_between(from, to, &mut states)
+    }
+
+    // Counts paths visiting every waypoint, in any order
+    pub fn count_paths_through(&self, from: &str, to: &str, waypoints: &[&str]) -> Result<u64> {
+        let mut total: u64 = 0;
+        for order in waypoints.iter().permutations(waypoints.len()) {
+            let stops = [from]
+                .into_iter()
+                .chain(order.into_iter().copied())
+                .chain([to])
+                .collect_vec();
+
+            let mut count: u64 = 1;
+            for (start, end) in stops.iter().tuple_windows() {
+                if count == 0 {
+                    break;
+                }
+                count = count
+                    .checked_mul(self.count_paths(start, end)?)
+                    .ok_or_else(|| anyhow!("Path count exceeds u64"))?;
+            }
+
+            total = total
+                .checked_add(count)
+                .ok_or_else(|| anyhow!("Path count exceeds u64"))?;
+        }
+
+        Ok(total)
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph {\n");
+        for (id, name) in self.names.iter().enumerate() {
+            if self.edges[id].is_empty() {
+                dot.push_str(&format!("    \"{}\";\n", name));
+            }
+            for &next in self.edges[id].iter() {
+                dot.push_str(&format!("    \"{}\" -> \"{}\";\n", name, self.names[next]));
+            }
+        }
+        dot.push('}');
+
+        dot
+    }
+
+    fn require_node(&self, name: &str) -> Result<NodeId> {
+        self.node_id(name)
+            .ok_or_else(|| anyhow!("Node {} is not part of the graph", name))
+    }
+
+    fn count_paths_between(
+        &self,
+        current: NodeId,
+        to: NodeId,
+        states: &mut HashMap<NodeId, VisitState>,
+    ) -> Result<u64> {
+        if current == to {
+            return Ok(1);
+        }
+
+        match states.get(&current) {
+            Some(VisitState::Done(count)) => return Ok(*count),
+            Some(VisitState::InProgress) => bail!(
+                "Graph contains a cycle through {}, paths cannot be counted",
+                self.names[current]
+            ),
+            None => {}
+        }
+
+        states.insert(current, VisitState::InProgress);
+        let mut count: u64 = 0;
+        for &next in self.edges[current].iter() {
+            count = count
+                .checked_add(self.count_paths_between(next, to, states)?)
+                .ok_or_else(|| anyhow!("Path count exceeds u64"))?;
+        }
+        states.insert(current, VisitState::Done(count));
+
+        Ok(count)
+    }
+
+    fn describe_cycle(&self, candidates: &HashSet<NodeId>) -> String {
+        // Every node left over by Kahn's algorithm has a predecessor that is left over as
+        // well, so walking backwards eventually repeats a node.
+        let mut predecessors: HashMap<NodeId, NodeId> = HashMap::new();
+        for (from, targets) in self.edges.iter().enumerate() {
+            for to in targets.iter() {
+                if candidates.contains(&from) && candidates.contains(to) {
+                    predecessors.entry(*to).or_insert(from);
+                }
+            }
+        }
+
+        let Some(&start) = candidates.iter().min() else {
+            return String::new();
+        };
+
+        let mut path = vec![start];
+        let mut seen = HashMap::from([(start, 0)]);
+        let mut current = start;
+        loop {
+            current = predecessors[&current];
+            if let Some(&idx) = seen.get(&current) {
+                let mut cycle = path[idx..].to_vec();
+                cycle.reverse();
+                cycle.rotate_right(1);
+                cycle.push(cycle[0]);
+                return cycle.iter().map(|&id| self.names[id].as_str()).join(" -> ");
+            }
+            seen.insert(current, path.len());
+            path.push(current);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::Graph;
+
+    fn graph(edges: &[(&str, &str)]) -> Graph {
+        let mut graph = Graph::new();
+        for (from, to) in edges {
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+
+    #[test]
+    fn nodes_are_interned() {
+        let graph = graph(&[("a", "b"), ("b", "c"), ("a", "b")]);
+
+        assert_eq!(graph.len(), 3);
+        assert_eq!(graph.node_id("b"), Some(1));
+        assert_eq!(graph.name(2), "c");
+        assert_eq!(graph.neighbours(0), [1]);
+    }
+
+    #[test]
+    fn toposort_orders_dependencies() {
+        let graph = graph(&[
+            ("shirt", "tie"),
+            ("tie", "jacket"),
+            ("shirt", "belt"),
+            ("belt", "jacket"),
+        ]);
+
+        let order = graph
+            .toposort()
+            .unwrap()
+            .into_iter()
+            .map(|id| graph.name(id))
+            .collect::<Vec<_>>();
+        assert_eq!(order, ["shirt", "tie", "belt", "jacket"]);
+    }
+
+    #[test]
+    fn toposort_reports_cycle() {
+        let graph = graph(&[("start", "a"), ("a", "b"), ("b", "c"), ("c", "a")]);
+
+        let err = graph.toposort().unwrap_err();
+        assert_eq!(err.to_string(), "Graph contains a cycle: a -> b -> c -> a");
+    }
+
+    #[test]
+    fn reachability() {
+        let graph = graph(&[("a", "b"), ("b", "c"), ("d", "a")]);
+
+        let reachable = graph.reachable_from(graph.node_id("a").unwrap());
+        let names = reachable
+            .iter()
+            .map(|&id| graph.name(id))
+            .collect::<HashSet<_>>();
+        assert_eq!(names, HashSet::from(["b", "c"]));
+    }
+
+    #[test]
+    fn count_paths_with_waypoints() {
+        let graph = graph(&[
+            ("svr", "aaa"),
+            ("svr", "bbb"),
+            ("aaa", "fft"),
+            ("bbb", "fft"),
+            ("bbb", "out"),
+            ("fft", "ccc"),
+            ("ccc", "dac"),
+            ("ccc", "out"),
+            ("dac", "out"),
+        ]);
+
+        assert_eq!(graph.count_paths("svr", "out").unwrap(), 5);
+        assert_eq!(
+            graph
+                .count_paths_through("svr", "out", &["dac", "fft"])
+                .unwrap(),
+            2
+        );
+        assert!(graph.count_paths("svr", "zzz").is_err());
+    }
+
+    #[test]
+    fn dot_export() {
+        let mut graph = graph(&[("a", "b")]);
+        graph.add_node("c");
+
+        assert_eq!(
+            graph.to_dot(),
+            "digraph {\n    \"a\" -> \"b\";\n    \"b\";\n    \"c\";\n}"
+        );
+    }
+}
@@ -0,0 +1,273 @@
+use std::{
+    cmp::{max, min},
+    ops::{Add, Range, Sub},
+    slice,
+};
+
+use num::Zero;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Range<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, Range<T>> {
+        self.intervals.iter()
+    }
+
+    pub fn min_value(&self) -> Option<T> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let first = self.intervals.partition_point(|i| i.end < range.start);
+        let last = self.intervals.partition_point(|i| i.start <= range.end);
+
+        let mut merged = range;
+        if first < last {
+            merged.start = min(merged.start, self.intervals[first].start);
+            merged.end = max(merged.end, self.intervals[last - 1].end);
+        }
+
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let first = self.intervals.partition_point(|i| i.end <= range.start);
+        let last = self.intervals.partition_point(|i| i.start < range.end);
+        if first >= last {
+            return;
+        }
+
+        let mut remaining = Vec::with_capacity(2);
+        if self.intervals[first].start < range.start {
+            remaining.push(self.intervals[first].start..range.start);
+        }
+        if self.intervals[last - 1].end > range.end {
+            remaining.push(range.end..self.intervals[last - 1].end);
+        }
+
+        self.intervals.splice(first..last, remaining);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let idx = self.intervals.partition_point(|i| i.end <= value);
+        self.intervals.get(idx).is_some_and(|i| i.start <= value)
+    }
+
+    pub fn contains_range(&self, range: Range<T>) -> bool {
+        if range.is_empty() {
+            return true;
+        }
+
+        let idx = self.intervals.partition_point(|i| i.end <= range.start);
+        self.intervals
+            .get(idx)
+            .is_some_and(|i| i.start <= range.start && range.end <= i.end)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for range in other.iter() {
+            result.insert(range.clone());
+        }
+
+        result
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for range in other.iter() {
+            result.remove(range.clone());
+        }
+
+        result
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let mut left = self.intervals.iter().peekable();
+        let mut right = other.intervals.iter().peekable();
+
+        while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+            let start = max(l.start, r.start);
+            let end = min(l.end, r.end);
+            if start < end {
+                intervals.push(start..end);
+            }
+
+            if l.end < r.end {
+                left.next();
+            } else {
+                right.next();
+            }
+        }
+
+        Self { intervals }
+    }
+}
+
+impl<T: Copy + Ord + Zero + Sub<Output = T>> IntervalSet<T> {
+    pub fn covered_length(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::zero(), |acc, i| acc + (i.end - i.start))
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+
+        set
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PiecewiseMapping<T> {
+    pieces: Vec<(Range<T>, T)>,
+}
+
+impl<T> Default for PiecewiseMapping<T> {
+    fn default() -> Self {
+        Self { pieces: Vec::new() }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> PiecewiseMapping<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, source: Range<T>, destination: T) {
+        self.pieces.push((source, destination));
+    }
+
+    pub fn map_value(&self, value: T) -> T {
+        self.pieces
+            .iter()
+            .find(|(source, _)| source.contains(&value))
+            .map_or(value, |(source, destination)| {
+                *destination + (value - source.start)
+            })
+    }
+
+    pub fn map_set(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut remaining = set.clone();
+        let mut mapped = IntervalSet::new();
+
+        for (source, destination) in self.pieces.iter() {
+            let source_set = IntervalSet::from_iter([source.clone()]);
+            for overlap in remaining.intersection(&source_set).iter() {
+                let start = *destination + (overlap.start - source.start);
+                let end = *destination + (overlap.end - source.start);
+                mapped.insert(start..end);
+            }
+            remaining.remove(source.clone());
+        }
+
+        mapped.union(&remaining)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IntervalSet, PiecewiseMapping};
+
+    fn intervals(set: &IntervalSet<u64>) -> Vec<(u64, u64)> {
+        set.iter().map(|r| (r.start, r.end)).collect()
+    }
+
+    #[test]
+    fn insert_merges_overlapping_and_adjacent_intervals() {
+        let mut set = IntervalSet::new();
+        set.insert(10..20);
+        set.insert(30..40);
+        set.insert(20..25);
+        set.insert(0..0);
+
+        assert_eq!(intervals(&set), [(10, 25), (30, 40)]);
+
+        set.insert(5..35);
+        assert_eq!(intervals(&set), [(5, 40)]);
+    }
+
+    #[test]
+    fn remove_splits_intervals() {
+        let mut set = IntervalSet::from_iter([0..10, 20..30]);
+        set.remove(5..25);
+
+        assert_eq!(intervals(&set), [(0, 5), (25, 30)]);
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = IntervalSet::from_iter([0..10, 20..30]);
+        let b = IntervalSet::from_iter([5..25, 28..40]);
+
+        assert_eq!(intervals(&a.union(&b)), [(0, 40)]);
+        assert_eq!(
+            intervals(&a.intersection(&b)),
+            [(5, 10), (20, 25), (28, 30)]
+        );
+        assert_eq!(intervals(&a.difference(&b)), [(0, 5), (25, 28)]);
+    }
+
+    #[test]
+    fn containment_and_length() {
+        let set = IntervalSet::from_iter([3..6, 10..15, 16..21]);
+
+        assert!(set.contains(3));
+        assert!(!set.contains(6));
+        assert!(set.contains(20));
+        assert!(!set.contains(15));
+        assert!(set.contains_range(11..15));
+        assert!(!set.contains_range(11..17));
+        assert_eq!(set.covered_length(), 13);
+        assert_eq!(set.min_value(), Some(3));
+    }
+
+    #[test]
+    fn piecewise_mapping_splits_at_boundaries() {
+        let mut mapping = PiecewiseMapping::new();
+        mapping.add(98..100, 50);
+        mapping.add(50..98, 52);
+
+        assert_eq!(mapping.map_value(79), 81);
+        assert_eq!(mapping.map_value(99), 51);
+        assert_eq!(mapping.map_value(10), 10);
+
+        let seeds = IntervalSet::from_iter([45..55, 97..102]);
+        let mapped = mapping.map_set(&seeds);
+
+        assert_eq!(intervals(&mapped), [(45, 57), (99, 102)]);
+    }
+}
@@ -0,0 +1,462 @@
+use anyhow::{ensure, Result};
+use num::{integer::lcm, rational::Ratio, Integer, Zero};
+
+pub type Rational = Ratio<i128>;
+
+#[derive(Clone, Debug)]
+struct PivotExpression {
+    variable: usize,
+    constant: Rational,
+    free_coefficients: Vec<Rational>,
+}
+
+#[derive(Clone, Debug)]
+pub struct SolutionSpace {
+    variable_count: usize,
+    free_variables: Vec<usize>,
+    pivots: Vec<PivotExpression>,
+}
+
+// Integer scaled form of a pivot: denominator * x = constant + sum(coefficients * free)
+struct ScaledPivot {
+    variable: usize,
+    denominator: i128,
+    constant: i128,
+    coefficients: Vec<i128>,
+}
+
+struct Search<'a> {
+    free_variables: Vec<usize>,
+    free_bounds: Vec<i128>,
+    pivots: Vec<ScaledPivot>,
+    upper_bounds: &'a [u64],
+    objective: Option<(i128, Vec<i128>)>,
+    objective_scale: i128,
+}
+
+pub fn solve_linear_system(
+    coefficients: &[Vec<i64>],
+    constants: &[i64],
+) -> Result<Option<SolutionSpace>> {
+    ensure!(
+        coefficients.len() == constants.len(),
+        "Expected {} constants but found {}",
+        coefficients.len(),
+        constants.len()
+    );
+
+    let variable_count = coefficients.first().map_or(0, |row| row.len());
+    ensure!(
+        coefficients.iter().all(|row| row.len() == variable_count),
+        "All rows of the coefficient matrix need {} columns",
+        variable_count
+    );
+
+    let mut rows = coefficients
+        .iter()
+        .zip(constants)
+        .map(|(row, constant)| {
+            row.iter()
+                .chain([constant])
+                .map(|&value| Rational::from_integer(value.into()))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut pivot_columns = Vec::new();
+    let mut free_variables = Vec::new();
+    for column in 0..variable_count {
+        let pivot_row = pivot_columns.len();
+        let Some(found_row) = (pivot_row..rows.len()).find(|&row| !rows[row][column].is_zero())
+        else {
+            free_variables.push(column);
+            continue;
+        };
+
+        rows.swap(pivot_row, found_row);
+        let pivot_value = rows[pivot_row][column];
+        for value in rows[pivot_row].iter_mut() {
+            *value /= pivot_value;
+        }
+
+        let pivot_values = rows[pivot_row].clone();
+        for (row_idx, row) in rows.iter_mut().enumerate() {
+            let factor = row[column];
+            if row_idx == pivot_row || factor.is_zero() {
+                continue;
+            }
+
+            for (value, pivot_value) in row.iter_mut().zip(&pivot_values).skip(column) {
+                *value -= pivot_value * factor;
+            }
+        }
+
+        pivot_columns.push(column);
+    }
+
+    let is_inconsistent = rows
+        .iter()
+        .skip(pivot_columns.len())
+        .any(|row| !row[variable_count].is_zero());
+    if is_inconsistent {
+        return Ok(None);
+    }
+
+    let pivots = pivot_columns
+        .iter()
+        .zip(&rows)
+        .map(|(&variable, row)| PivotExpression {
+            variable,
+            constant: row[variable_count],
+            free_coefficients: free_variables.iter().map(|&free| -row[free]).collect(),
+        })
+        .collect();
+
+    Ok(Some(SolutionSpace {
+        variable_count,
+        free_variables,
+        pivots,
+    }))
+}
+
+impl SolutionSpace {
+    pub fn variable_count(&self) -> usize {
+        self.variable_count
+    }
+
+    pub fn free_variables(&self) -> &[usize] {
+        &self.free_variables
+    }
+
+    pub fn is_unique(&self) -> bool {
+        self.free_variables.is_empty()
+    }
+
+    pub fn evaluate(&self, free_values: &[Rational]) -> Result<Vec<Rational>> {
+        ensure!(
+            free_values.len() == self.free_variables.len(),
+            "Expected {} free values but found {}",
+            self.free_variables.len(),
+            free_values.len()
+        );
+
+        let mut values = vec![Rational::zero(); self.variable_count];
+        for (&variable, &value) in self.free_variables.iter().zip(free_values) {
+            values[variable] = value;
+        }
+
+        for pivot in self.pivots.iter() {
+            values[pivot.variable] = pivot
+                .free_coefficients
+                .iter()
+                .zip(free_values)
+                .fold(pivot.constant, |acc, (&coefficient, &value)| {
+                    acc + coefficient * value
+                });
+        }
+
+        Ok(values)
+    }
+
+    pub fn integer_solutions(&self, upper_bounds: &[u64]) -> Result<Vec<Vec<u64>>> {
+        let search = self.prepare_search(upper_bounds, None)?;
+
+        let mut solutions = Vec::new();
+        search.branch(&mut Vec::new(), &mut None, &mut |values, _| {
+            solutions.push(values);
+        });
+
+        Ok(solutions)
+    }
+
+    pub fn minimize(
+        &self,
+        weights: &[i64],
+        upper_bounds: &[u64],
+    ) -> Result<Option<(i64, Vec<u64>)>> {
+        ensure!(
+            weights.len() == self.variable_count,
+            "Expected {} weights but found {}",
+            self.variable_count,
+            weights.len()
+        );
+
+        let search = self.prepare_search(upper_bounds, Some(weights))?;
+
+        let mut best = None;
+        search.branch(&mut Vec::new(), &mut None, &mut |values, cost| {
+            best = Some((cost, values));
+        });
+
+        best.map(|(cost, values)| Ok((i64::try_from(cost / search.objective_scale)?, values)))
+            .transpose()
+    }
+
+    fn prepare_search<'a>(
+        &self,
+        upper_bounds: &'a [u64],
+        weights: Option<&[i64]>,
+    ) -> Result<Search<'a>> {
+        ensure!(
+            upper_bounds.len() == self.variable_count,
+            "Expected {} upper bounds but found {}",
+            self.variable_count,
+            upper_bounds.len()
+        );
+
+        let pivots = self
+            .pivots
+            .iter()
+            .map(|pivot| {
+                let denominator = pivot
+                    .free_coefficients
+                    .iter()
+                    .fold(*pivot.constant.denom(), |acc, c| lcm(acc, *c.denom()));
+                let scale = |value: &Rational| (value * denominator).to_integer();
+
+                ScaledPivot {
+                    variable: pivot.variable,
+                    denominator,
+                    constant: scale(&pivot.constant),
+                    coefficients: pivot.free_coefficients.iter().map(scale).collect(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let objective_scale = pivots.iter().fold(1, |acc, p| lcm(acc, p.denominator));
+        let objective = weights.map(|weights| {
+            let mut constant = 0;
+            let mut coefficients = self
+                .free_variables
+                .iter()
+                .map(|&free| i128::from(weights[free]) * objective_scale)
+                .collect::<Vec<_>>();
+
+            for pivot in pivots.iter() {
+                let factor =
+                    i128::from(weights[pivot.variable]) * objective_scale / pivot.denominator;
+                constant += factor * pivot.constant;
+                for (coefficient, pivot_coefficient) in
+                    coefficients.iter_mut().zip(&pivot.coefficients)
+                {
+                    *coefficient += factor * pivot_coefficient;
+                }
+            }
+
+            (constant, coefficients)
+        });
+
+        Ok(Search {
+            free_variables: self.free_variables.clone(),
+            free_bounds: self
+                .free_variables
+                .iter()
+                .map(|&free| i128::from(upper_bounds[free]))
+                .collect(),
+            pivots,
+            upper_bounds,
+            objective,
+            objective_scale,
+        })
+    }
+}
+
+impl Search<'_> {
+    // Calls `on_solution` with every non-negative integer solution. With an objective, only
+    // improvements over the incumbent are reported and branches that cannot beat it are pruned.
+    fn branch<F>(
+        &self,
+        free_values: &mut Vec<i128>,
+        incumbent: &mut Option<i128>,
+        on_solution: &mut F,
+    ) where
+        F: FnMut(Vec<u64>, i128),
+    {
+        if !self.is_feasible(free_values) {
+            return;
+        }
+
+        let lower_bound = self.lower_bound(free_values);
+        if lower_bound.is_some_and(|lower| incumbent.is_some_and(|best| lower >= best)) {
+            return;
+        }
+
+        let depth = free_values.len();
+        if depth == self.free_bounds.len() {
+            let Some(values) = self.values_for(free_values) else {
+                return;
+            };
+
+            let cost = lower_bound.unwrap_or(0);
+            if self.objective.is_some() {
+                *incumbent = Some(cost);
+            }
+            on_solution(values, cost);
+            return;
+        }
+
+        let ascending = self
+            .objective
+            .as_ref()
+            .is_none_or(|(_, coefficients)| coefficients[depth] >= 0);
+        let candidates: Box<dyn Iterator<Item = i128>> = if ascending {
+            Box::new(0..=self.free_bounds[depth])
+        } else {
+            Box::new((0..=self.free_bounds[depth]).rev())
+        };
+
+        for value in candidates {
+            free_values.push(value);
+            self.branch(free_values, incumbent, on_solution);
+            free_values.pop();
+        }
+    }
+
+    fn is_feasible(&self, free_values: &[i128]) -> bool {
+        self.pivots.iter().all(|pivot| {
+            let (min, max) = self.value_range(pivot.constant, &pivot.coefficients, free_values);
+            let upper_bound = i128::from(self.upper_bounds[pivot.variable]) * pivot.denominator;
+
+            max >= 0 && min <= upper_bound
+        })
+    }
+
+    fn value_range(
+        &self,
+        constant: i128,
+        coefficients: &[i128],
+        free_values: &[i128],
+    ) -> (i128, i128) {
+        let mut min = constant;
+        let mut max = constant;
+        for (idx, coefficient) in coefficients.iter().enumerate() {
+            match free_values.get(idx) {
+                Some(value) => {
+                    min += coefficient * value;
+                    max += coefficient * value;
+                }
+                None => {
+                    let extreme = coefficient * self.free_bounds[idx];
+                    min += extreme.min(0);
+                    max += extreme.max(0);
+                }
+            }
+        }
+
+        (min, max)
+    }
+
+    fn lower_bound(&self, free_values: &[i128]) -> Option<i128> {
+        let (constant, coefficients) = self.objective.as_ref()?;
+        Some(self.value_range(*constant, coefficients, free_values).0)
+    }
+
+    fn values_for(&self, free_values: &[i128]) -> Option<Vec<u64>> {
+        let mut values = vec![0; self.upper_bounds.len()];
+        for (&variable, &value) in self.free_variables.iter().zip(free_values) {
+            values[variable] = u64::try_from(value).ok()?;
+        }
+
+        for pivot in self.pivots.iter() {
+            let scaled = pivot.constant
+                + pivot
+                    .coefficients
+                    .iter()
+                    .zip(free_values)
+                    .map(|(c, v)| c * v)
+                    .sum::<i128>();
+            let (value, remainder) = scaled.div_rem(&pivot.denominator);
+            if remainder != 0 || value < 0 {
+                return None;
+            }
+
+            values[pivot.variable] = u64::try_from(value).ok()?;
+        }
+
+        Some(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{solve_linear_system, Rational};
+
+    #[test]
+    fn unique_solution() {
+        let space = solve_linear_system(&[vec![94, 22], vec![34, 67]], &[8400, 5400])
+            .unwrap()
+            .unwrap();
+
+        assert!(space.is_unique());
+        let values = space.evaluate(&[]).unwrap();
+        assert_eq!(
+            values,
+            [Rational::from_integer(80), Rational::from_integer(40)]
+        );
+    }
+
+    #[test]
+    fn inconsistent_system() {
+        let space = solve_linear_system(&[vec![1, 1], vec![2, 2]], &[1, 3]).unwrap();
+        assert!(space.is_none());
+    }
+
+    #[test]
+    fn rational_solution_is_not_an_integer_solution() {
+        let space = solve_linear_system(&[vec![2, 0], vec![0, 3]], &[1, 3])
+            .unwrap()
+            .unwrap();
+
+        let values = space.evaluate(&[]).unwrap();
+        assert_eq!(values[0], Rational::new(1, 2));
+        assert!(space.integer_solutions(&[10, 10]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn enumerate_free_variables_within_bounds() {
+        let space = solve_linear_system(&[vec![1, 1, 1]], &[2])
+            .unwrap()
+            .unwrap();
+        assert_eq!(space.free_variables(), [1, 2]);
+
+        let mut solutions = space.integer_solutions(&[2, 2, 2]).unwrap();
+        solutions.sort();
+        assert_eq!(
+            solutions,
+            [
+                vec![0, 0, 2],
+                vec![0, 1, 1],
+                vec![0, 2, 0],
+                vec![1, 0, 1],
+                vec![1, 1, 0],
+                vec![2, 0, 0]
+            ]
+        );
+    }
+
+    #[test]
+    fn minimize_weighted_sum() {
+        let coefficients = [
+            vec![0, 0, 0, 0, 1, 1],
+            vec![0, 1, 0, 0, 0, 1],
+            vec![0, 0, 1, 1, 1, 0],
+            vec![1, 1, 0, 1, 0, 0],
+        ];
+        let space = solve_linear_system(&coefficients, &[3, 5, 4, 7])
+            .unwrap()
+            .unwrap();
+
+        let (cost, values) = space.minimize(&[1; 6], &[7; 6]).unwrap().unwrap();
+        assert_eq!(cost, 10);
+        for (row, constant) in coefficients.iter().zip([3, 5, 4, 7]) {
+            let sum: u64 = row.iter().zip(&values).map(|(&c, &v)| c as u64 * v).sum();
+            assert_eq!(sum, constant);
+        }
+
+        let (cost, _) = space
+            .minimize(&[3, 1, 1, 1, 1, 1], &[7; 6])
+            .unwrap()
+            .unwrap();
+        assert!(cost >= 10);
+    }
+}
@@ -32,6 +32,115 @@ impl<T> Grid<T> {
         }
         Some(&self.values[point.y][point.x])
     }
+
+    pub fn map<U, F>(&self, mapper: F) -> Grid<U>
+    where
+        F: Fn(&T) -> U,
+    {
+        let values = self
+            .values
+            .iter()
+            .map(|row| row.iter().map(&mapper).collect())
+            .collect();
+
+        Grid::from_rectangular_values(values)
+    }
+
+    pub fn widen<U, F, const N: usize>(&self, mapper: F) -> Grid<U>
+    where
+        F: Fn(&T) -> [U; N],
+    {
+        let values = self
+            .values
+            .iter()
+            .map(|row| row.iter().flat_map(&mapper).collect())
+            .collect();
+
+        Grid::from_rectangular_values(values)
+    }
+
+    fn from_rectangular_values(values: Vec<Vec<T>>) -> Self {
+        let height = values.len();
+        let width = values.first().map_or(0, Vec::len);
+        let dim = Dimensions::new(height as u64, width as u64);
+        Self { values, dim }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn transpose(&self) -> Self {
+        let width = self.udims().width;
+        let values = (0..width)
+            .map(|x| self.values.iter().map(|row| row[x].clone()).collect())
+            .collect();
+
+        Self::from_rectangular_values(values)
+    }
+
+    pub fn flip_horizontal(&self) -> Self {
+        let values = self
+            .values
+            .iter()
+            .map(|row| row.iter().rev().cloned().collect())
+            .collect();
+
+        Self::from_rectangular_values(values)
+    }
+
+    pub fn flip_vertical(&self) -> Self {
+        Self::from_rectangular_values(self.values.iter().rev().cloned().collect())
+    }
+
+    pub fn rotate_90(&self) -> Self {
+        self.transpose().flip_horizontal()
+    }
+
+    pub fn rotate_180(&self) -> Self {
+        self.flip_horizontal().flip_vertical()
+    }
+
+    pub fn rotate_270(&self) -> Self {
+        self.transpose().flip_vertical()
+    }
+
+    pub fn sub_grid(&self, top_left: UPoint, dim: UDimensions) -> Result<Self> {
+        let grid_dim = self.udims();
+        let fits_width = top_left
+            .x
+            .checked_add(dim.width)
+            .is_some_and(|x| x <= grid_dim.width);
+        let fits_height = top_left
+            .y
+            .checked_add(dim.height)
+            .is_some_and(|y| y <= grid_dim.height);
+        if !fits_width || !fits_height {
+            bail!(
+                "Sub grid of width {} and height {} at {:?} exceeds grid of width {} and height {}",
+                dim.width,
+                dim.height,
+                top_left,
+                grid_dim.width,
+                grid_dim.height
+            );
+        }
+
+        let values = self.values[top_left.y..top_left.y + dim.height]
+            .iter()
+            .map(|row| row[top_left.x..top_left.x + dim.width].to_vec())
+            .collect();
+
+        Ok(Self::from_rectangular_values(values))
+    }
+
+    pub fn tile(&self, horizontal: usize, vertical: usize) -> Self {
+        let row_count = if horizontal == 0 { 0 } else { vertical };
+        let values = (0..row_count)
+            .flat_map(|_| self.values.iter())
+            .map(|row| (0..horizontal).flat_map(|_| row.iter().cloned()).collect())
+            .collect();
+
+        Self::from_rectangular_values(values)
+    }
 }
 
 impl<T> Index<Point<i32>> for Grid<T> {
@@ -197,3 +306,89 @@ impl Dimensions {
         Ok(Dimensions::new(values.len().try_into()?, width.try_into()?))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::common::math_2d::{UDimensions, UPoint};
+
+    use super::Grid;
+
+    fn create_grid() -> Grid<u8> {
+        Grid::from_raw_values(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap()
+    }
+
+    fn rows(grid: &Grid<u8>) -> Vec<Vec<u8>> {
+        grid.values.clone()
+    }
+
+    #[test]
+    fn transpose_swaps_dimensions() {
+        let grid = create_grid().transpose();
+
+        assert_eq!(rows(&grid), [[1, 4], [2, 5], [3, 6]]);
+        assert_eq!(grid.dim().width, 2);
+        assert_eq!(grid.dim().height, 3);
+    }
+
+    #[test]
+    fn rotate_clockwise() {
+        let grid = create_grid();
+
+        assert_eq!(rows(&grid.rotate_90()), [[4, 1], [5, 2], [6, 3]]);
+        assert_eq!(rows(&grid.rotate_180()), [[6, 5, 4], [3, 2, 1]]);
+        assert_eq!(rows(&grid.rotate_270()), [[3, 6], [2, 5], [1, 4]]);
+        assert_eq!(rows(&grid.rotate_90().rotate_270()), rows(&grid));
+    }
+
+    #[test]
+    fn flip_grid() {
+        let grid = create_grid();
+
+        assert_eq!(rows(&grid.flip_horizontal()), [[3, 2, 1], [6, 5, 4]]);
+        assert_eq!(rows(&grid.flip_vertical()), [[4, 5, 6], [1, 2, 3]]);
+    }
+
+    #[test]
+    fn sub_grid_inside_bounds() {
+        let grid = create_grid();
+        let dim = UDimensions {
+            width: 2,
+            height: 2,
+        };
+
+        let sub_grid = grid.sub_grid(UPoint::new(1, 0), dim).unwrap();
+        assert_eq!(rows(&sub_grid), [[2, 3], [5, 6]]);
+        assert_eq!(sub_grid.udims(), dim);
+
+        assert!(grid.sub_grid(UPoint::new(2, 0), dim).is_err());
+    }
+
+    #[test]
+    fn map_and_widen_cells() {
+        let grid = create_grid();
+
+        let mapped = grid.map(|v| v * 2);
+        assert_eq!(rows(&mapped), [[2, 4, 6], [8, 10, 12]]);
+
+        let widened = grid.widen(|v| [*v, 0]);
+        assert_eq!(rows(&widened), [[1, 0, 2, 0, 3, 0], [4, 0, 5, 0, 6, 0]]);
+        assert_eq!(widened.dim().width, 6);
+    }
+
+    #[test]
+    fn tile_grid() {
+        let grid = create_grid().tile(2, 2);
+
+        assert_eq!(
+            rows(&grid),
+            [
+                [1, 2, 3, 1, 2, 3],
+                [4, 5, 6, 4, 5, 6],
+                [1, 2, 3, 1, 2, 3],
+                [4, 5, 6, 4, 5, 6]
+            ]
+        );
+        assert_eq!(grid.dim().width, 6);
+        assert_eq!(grid.dim().height, 4);
+    }
+}
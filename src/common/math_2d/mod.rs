@@ -1,10 +1,24 @@
 mod grid;
 mod point;
+mod sparse_grid;
 mod vector;
 
+use std::ops::Sub;
+
 pub use crate::common::math_2d::grid::Dimensions;
 pub use crate::common::math_2d::grid::Grid;
 pub use crate::common::math_2d::grid::PointIdx;
+pub use crate::common::math_2d::grid::UDimensions;
 pub use crate::common::math_2d::point::Point;
 pub use crate::common::math_2d::point::UPoint;
+pub use crate::common::math_2d::sparse_grid::BoundingBox;
+pub use crate::common::math_2d::sparse_grid::SparseGrid;
 pub use crate::common::math_2d::vector::Vec2;
+
+pub(crate) fn abs_diff<T: PartialOrd + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
@@ -1,33 +1,13 @@
 use std::{
     fmt::Debug,
-    ops::{Add, AddAssign, Rem, Sub},
+    ops::{Add, AddAssign, Mul, Neg, Rem, Sub, SubAssign},
 };
 
-use super::Vec2;
+use num::{CheckedAdd, CheckedSub};
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-pub struct UPoint {
-    pub x: usize,
-    pub y: usize,
-}
-
-impl UPoint {
-    pub fn new(x: usize, y: usize) -> Self {
-        Self { x, y }
-    }
+use super::{abs_diff, Vec2};
 
-    pub fn checked_sub(&self, other: UPoint) -> Option<Self> {
-        let x = self.x.checked_sub(other.x)?;
-        let y = self.y.checked_sub(other.y)?;
-        Some(UPoint::new(x, y))
-    }
-
-    pub fn checked_add(&self, other: UPoint) -> Option<Self> {
-        let x = self.x.checked_add(other.x)?;
-        let y = self.y.checked_add(other.y)?;
-        Some(UPoint::new(x, y))
-    }
-}
+pub type UPoint = Point<usize>;
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Point<T> {
@@ -45,6 +25,64 @@ impl<T> Point<T> {
     pub fn new(x: T, y: T) -> Self {
         Self { x, y }
     }
+
+    pub fn cast<U: From<T>>(self) -> Point<U> {
+        Point::new(self.x.into(), self.y.into())
+    }
+
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<Point<U>> {
+        let x = U::try_from(self.x).ok()?;
+        let y = U::try_from(self.y).ok()?;
+        Some(Point::new(x, y))
+    }
+}
+
+impl<T: CheckedAdd + CheckedSub> Point<T> {
+    pub fn checked_add(&self, rhs: Vec2<T>) -> Option<Self> {
+        let x = self.x.checked_add(&rhs.x)?;
+        let y = self.y.checked_add(&rhs.y)?;
+        Some(Point::new(x, y))
+    }
+
+    pub fn checked_sub(&self, rhs: Vec2<T>) -> Option<Self> {
+        let x = self.x.checked_sub(&rhs.x)?;
+        let y = self.y.checked_sub(&rhs.y)?;
+        Some(Point::new(x, y))
+    }
+}
+
+impl<T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>> Point<T> {
+    pub fn manhattan_distance(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    pub fn chebyshev_distance(self, other: Self) -> T {
+        let dx = abs_diff(self.x, other.x);
+        let dy = abs_diff(self.y, other.y);
+        if dx > dy {
+            dx
+        } else {
+            dy
+        }
+    }
+}
+
+impl<T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T> + Mul<Output = T>> Point<T> {
+    pub fn euclidean_distance_squared(self, other: Self) -> T {
+        let dx = abs_diff(self.x, other.x);
+        let dy = abs_diff(self.y, other.y);
+        dx * dx + dy * dy
+    }
+}
+
+impl<T: Copy + Neg<Output = T> + Add<Output = T> + Sub<Output = T>> Point<T> {
+    pub fn rotate_clockwise_around(self, center: Self) -> Self {
+        center + (self - center).rotate_clockwise()
+    }
+
+    pub fn rotate_counter_clockwise_around(self, center: Self) -> Self {
+        center + (self - center).rotate_counter_clockwise()
+    }
 }
 
 impl<T: Add<Output = T>> Add<Vec2<T>> for Point<T> {
@@ -76,6 +114,21 @@ impl<T: Sub<Output = T>> Sub<Vec2<T>> for Point<T> {
     }
 }
 
+impl<T: SubAssign> SubAssign<Vec2<T>> for Point<T> {
+    fn sub_assign(&mut self, rhs: Vec2<T>) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Vec2<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vec2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
 impl<T: Rem<Output = T> + Clone> std::ops::Rem<T> for Point<T> {
     type Output = Self;
 
@@ -86,3 +139,55 @@ impl<T: Rem<Output = T> + Clone> std::ops::Rem<T> for Point<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Point, UPoint, Vec2};
+
+    #[test]
+    fn distances_between_points() {
+        let a = Point::new(1_i64, -2);
+        let b = Point::new(-3_i64, 5);
+
+        assert_eq!(a.manhattan_distance(b), 11);
+        assert_eq!(a.chebyshev_distance(b), 7);
+        assert_eq!(a.euclidean_distance_squared(b), 65);
+    }
+
+    #[test]
+    fn distances_between_unsigned_points() {
+        let a = UPoint::new(7, 2);
+        let b = UPoint::new(3, 5);
+
+        assert_eq!(a.manhattan_distance(b), 7);
+        assert_eq!(b.manhattan_distance(a), 7);
+    }
+
+    #[test]
+    fn convert_between_signed_and_unsigned() {
+        let point = Point::new(3_i32, 4);
+        assert_eq!(point.try_cast::<usize>(), Some(UPoint::new(3, 4)));
+        assert_eq!(Point::new(-1_i32, 4).try_cast::<usize>(), None);
+        assert_eq!(Point::new(3_u8, 4).cast::<i64>(), Point::new(3_i64, 4));
+    }
+
+    #[test]
+    fn checked_operations_on_unsigned_points() {
+        let point = UPoint::new(0, 3);
+        assert_eq!(point.checked_sub(Vec2::new(1, 0)), None);
+        assert_eq!(point.checked_add(Vec2::new(1, 0)), Some(UPoint::new(1, 3)));
+    }
+
+    #[test]
+    fn rotate_around_center() {
+        let center = Point::new(1, 1);
+        let point = Point::new(1, 0);
+
+        assert_eq!(point.rotate_clockwise_around(center), Point::new(2, 1));
+        assert_eq!(
+            point.rotate_counter_clockwise_around(center),
+            Point::new(0, 1)
+        );
+        assert_eq!(point - center, Vec2::UP);
+    }
+}
@@ -0,0 +1,252 @@
+use std::{
+    collections::{hash_map, HashMap},
+    ops::Index,
+};
+
+use anyhow::{anyhow, Result};
+
+use super::{Grid, Point, UPoint};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct BoundingBox {
+    pub min: Point<i64>,
+    pub max: Point<i64>,
+}
+
+impl BoundingBox {
+    fn from_point(point: Point<i64>) -> Self {
+        Self {
+            min: point,
+            max: point,
+        }
+    }
+
+    fn extend(&mut self, point: Point<i64>) {
+        self.min = Point::new(self.min.x.min(point.x), self.min.y.min(point.y));
+        self.max = Point::new(self.max.x.max(point.x), self.max.y.max(point.y));
+    }
+
+    fn is_on_edge(&self, point: Point<i64>) -> bool {
+        point.x == self.min.x
+            || point.x == self.max.x
+            || point.y == self.min.y
+            || point.y == self.max.y
+    }
+
+    pub fn width(&self) -> u64 {
+        self.min.x.abs_diff(self.max.x) + 1
+    }
+
+    pub fn height(&self) -> u64 {
+        self.min.y.abs_diff(self.max.y) + 1
+    }
+
+    pub fn contains(&self, point: Point<i64>) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+}
+
+#[derive(Clone)]
+pub struct SparseGrid<T> {
+    values: HashMap<Point<i64>, T>,
+    default: T,
+    bounds: Option<BoundingBox>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new(default: T) -> Self {
+        Self {
+            values: HashMap::new(),
+            default,
+            bounds: None,
+        }
+    }
+
+    pub fn default_value(&self) -> &T {
+        &self.default
+    }
+
+    pub fn bounds(&self) -> Option<BoundingBox> {
+        self.bounds
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn contains(&self, point: Point<i64>) -> bool {
+        self.values.contains_key(&point)
+    }
+
+    pub fn get(&self, point: Point<i64>) -> &T {
+        self.values.get(&point).unwrap_or(&self.default)
+    }
+
+    pub fn get_mut(&mut self, point: Point<i64>) -> Option<&mut T> {
+        self.values.get_mut(&point)
+    }
+
+    pub fn insert(&mut self, point: Point<i64>, value: T) -> Option<T> {
+        match self.bounds.as_mut() {
+            Some(bounds) => bounds.extend(point),
+            None => self.bounds = Some(BoundingBox::from_point(point)),
+        }
+
+        self.values.insert(point, value)
+    }
+
+    pub fn remove(&mut self, point: Point<i64>) -> Option<T> {
+        let value = self.values.remove(&point)?;
+
+        if self.bounds.is_some_and(|b| b.is_on_edge(point)) {
+            self.bounds = self.compute_bounds();
+        }
+
+        Some(value)
+    }
+
+    pub fn iter(&self) -> hash_map::Iter<'_, Point<i64>, T> {
+        self.values.iter()
+    }
+
+    pub fn points(&self) -> hash_map::Keys<'_, Point<i64>, T> {
+        self.values.keys()
+    }
+
+    fn compute_bounds(&self) -> Option<BoundingBox> {
+        let mut points = self.values.keys();
+        let mut bounds = BoundingBox::from_point(*points.next()?);
+        for point in points {
+            bounds.extend(*point);
+        }
+
+        Some(bounds)
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    pub fn to_grid(&self) -> Result<Grid<T>> {
+        let Some(bounds) = self.bounds else {
+            return Grid::from_raw_values(Vec::new());
+        };
+
+        let values = (bounds.min.y..=bounds.max.y)
+            .map(|y| {
+                (bounds.min.x..=bounds.max.x)
+                    .map(|x| self.get(Point::new(x, y)).clone())
+                    .collect()
+            })
+            .collect();
+
+        Grid::from_raw_values(values)
+    }
+}
+
+impl<T: Clone + PartialEq> SparseGrid<T> {
+    pub fn from_grid(grid: &Grid<T>, default: T) -> Result<Self> {
+        let mut sparse = SparseGrid::new(default);
+        let dim = grid.udims();
+
+        for y in 0..dim.height {
+            for x in 0..dim.width {
+                let Some(value) = grid.get(UPoint::new(x, y)) else {
+                    continue;
+                };
+                if *value == sparse.default {
+                    continue;
+                }
+
+                let point = UPoint::new(x, y)
+                    .try_cast::<i64>()
+                    .ok_or_else(|| anyhow!("Grid position ({}, {}) exceeds i64", x, y))?;
+                sparse.insert(point, value.clone());
+            }
+        }
+
+        Ok(sparse)
+    }
+}
+
+impl<T> Index<Point<i64>> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, index: Point<i64>) -> &Self::Output {
+        self.get(index)
+    }
+}
+
+impl<T> Extend<(Point<i64>, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Point<i64>, T)>>(&mut self, iter: I) {
+        for (point, value) in iter {
+            self.insert(point, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::math_2d::{Grid, Point, UPoint};
+
+    use super::{BoundingBox, SparseGrid};
+
+    #[test]
+    fn bounds_follow_inserted_and_removed_points() {
+        let mut grid = SparseGrid::new('.');
+        assert_eq!(grid.bounds(), None);
+
+        grid.insert(Point::new(2, -1), '#');
+        grid.insert(Point::new(-3, 4), '#');
+        grid.insert(Point::new(0, 0), '#');
+
+        let expected = BoundingBox {
+            min: Point::new(-3, -1),
+            max: Point::new(2, 4),
+        };
+        assert_eq!(grid.bounds(), Some(expected));
+        assert_eq!(expected.width(), 6);
+        assert_eq!(expected.height(), 6);
+
+        grid.remove(Point::new(-3, 4));
+        let expected = BoundingBox {
+            min: Point::new(0, -1),
+            max: Point::new(2, 0),
+        };
+        assert_eq!(grid.bounds(), Some(expected));
+    }
+
+    #[test]
+    fn missing_points_return_default() {
+        let mut grid = SparseGrid::new(0);
+        grid.insert(Point::new(1_000_000, -1_000_000), 5);
+
+        assert_eq!(grid[Point::new(1_000_000, -1_000_000)], 5);
+        assert_eq!(grid[Point::new(0, 0)], 0);
+        assert_eq!(grid.len(), 1);
+    }
+
+    #[test]
+    fn convert_to_and_from_dense_grid() {
+        let dense = Grid::from_raw_values(vec![
+            vec!['.', '#', '.'],
+            vec!['.', '.', '.'],
+            vec!['#', '.', '.'],
+        ])
+        .unwrap();
+
+        let sparse = SparseGrid::from_grid(&dense, '.').unwrap();
+        assert_eq!(sparse.len(), 2);
+        assert!(sparse.contains(Point::new(1, 0)));
+        assert!(sparse.contains(Point::new(0, 2)));
+
+        let cropped = sparse.to_grid().unwrap();
+        assert_eq!(cropped.udims().width, 2);
+        assert_eq!(cropped.udims().height, 3);
+        assert_eq!(cropped.get(UPoint::new(1, 0)), Some(&'#'));
+        assert_eq!(cropped.get(UPoint::new(0, 2)), Some(&'#'));
+        assert_eq!(cropped.get(UPoint::new(0, 0)), Some(&'.'));
+    }
+}
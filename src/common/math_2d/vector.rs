@@ -1,4 +1,4 @@
-use std::ops::{Add, Mul, Sub};
+use std::ops::{Add, Mul, Neg, Sub};
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub struct Vec2<T> {
@@ -19,6 +19,29 @@ impl Vec2<i32> {
     pub const RIGHT: Vec2<i32> = Self { x: 1, y: 0 };
 }
 
+impl<T: Copy + Add<Output = T> + Mul<Output = T>> Vec2<T> {
+    pub fn dot(self, other: Self) -> T {
+        self.x * other.x + self.y * other.y
+    }
+}
+
+impl<T: Copy + Sub<Output = T> + Mul<Output = T>> Vec2<T> {
+    pub fn cross(self, other: Self) -> T {
+        self.x * other.y - self.y * other.x
+    }
+}
+
+// The y axis points down like the rows of a grid, so clockwise turns UP into RIGHT.
+impl<T: Neg<Output = T>> Vec2<T> {
+    pub fn rotate_clockwise(self) -> Self {
+        Vec2::new(-self.y, self.x)
+    }
+
+    pub fn rotate_counter_clockwise(self) -> Self {
+        Vec2::new(self.y, -self.x)
+    }
+}
+
 impl<T: Add<Output = T>> Add for Vec2<T> {
     type Output = Self;
 
@@ -35,6 +58,14 @@ impl<T: Sub<Output = T>> Sub for Vec2<T> {
     }
 }
 
+impl<T: Neg<Output = T>> Neg for Vec2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
 impl<T: Mul<Output = T> + Clone> Mul<T> for Vec2<T> {
     type Output = Self;
 
@@ -45,3 +76,27 @@ impl<T: Mul<Output = T> + Clone> Mul<T> for Vec2<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Vec2;
+
+    #[test]
+    fn rotations_follow_grid_directions() {
+        assert_eq!(Vec2::UP.rotate_clockwise(), Vec2::RIGHT);
+        assert_eq!(Vec2::RIGHT.rotate_clockwise(), Vec2::DOWN);
+        assert_eq!(Vec2::UP.rotate_counter_clockwise(), Vec2::LEFT);
+        assert_eq!(Vec2::LEFT.rotate_counter_clockwise(), Vec2::DOWN);
+        assert_eq!(-Vec2::UP, Vec2::DOWN);
+    }
+
+    #[test]
+    fn dot_and_cross_product() {
+        let a = Vec2::new(2, 3);
+        let b = Vec2::new(4, -1);
+
+        assert_eq!(a.dot(b), 5);
+        assert_eq!(a.cross(b), -14);
+        assert_eq!(Vec2::RIGHT.cross(Vec2::DOWN), 1);
+    }
+}
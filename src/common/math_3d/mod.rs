@@ -0,0 +1,5 @@
+mod point;
+mod vector;
+
+pub use crate::common::math_3d::point::Point3;
+pub use crate::common::math_3d::vector::Vec3;
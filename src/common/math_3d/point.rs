@@ -0,0 +1,109 @@
+use std::{
+    fmt::Debug,
+    ops::{Add, AddAssign, Mul, Sub},
+};
+
+use crate::common::math_2d::abs_diff;
+
+use super::Vec3;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Debug> Debug for Point3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("({:?}, {:?}, {:?})", self.x, self.y, self.z))
+    }
+}
+
+impl<T> Point3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn cast<U: From<T>>(self) -> Point3<U> {
+        Point3::new(self.x.into(), self.y.into(), self.z.into())
+    }
+
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<Point3<U>> {
+        let x = U::try_from(self.x).ok()?;
+        let y = U::try_from(self.y).ok()?;
+        let z = U::try_from(self.z).ok()?;
+        Some(Point3::new(x, y, z))
+    }
+}
+
+impl<T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>> Point3<T> {
+    pub fn manhattan_distance(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
+
+    pub fn chebyshev_distance(self, other: Self) -> T {
+        [abs_diff(self.y, other.y), abs_diff(self.z, other.z)]
+            .into_iter()
+            .fold(
+                abs_diff(self.x, other.x),
+                |max, d| if d > max { d } else { max },
+            )
+    }
+}
+
+impl<T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T> + Mul<Output = T>> Point3<T> {
+    pub fn euclidean_distance_squared(self, other: Self) -> T {
+        let dx = abs_diff(self.x, other.x);
+        let dy = abs_diff(self.y, other.y);
+        let dz = abs_diff(self.z, other.z);
+        dx * dx + dy * dy + dz * dz
+    }
+}
+
+impl<T: Add<Output = T>> Add<Vec3<T>> for Point3<T> {
+    type Output = Point3<T>;
+
+    fn add(self, rhs: Vec3<T>) -> Self::Output {
+        Point3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: AddAssign> AddAssign<Vec3<T>> for Point3<T> {
+    fn add_assign(&mut self, rhs: Vec3<T>) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+
+impl<T: Sub<Output = T>> Sub<Vec3<T>> for Point3<T> {
+    type Output = Point3<T>;
+
+    fn sub(self, rhs: Vec3<T>) -> Self::Output {
+        Point3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Vec3<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vec3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Point3;
+
+    #[test]
+    fn distances_between_points() {
+        let a = Point3::new(162_i64, 817, 812);
+        let b = Point3::new(425_i64, 690, 689);
+
+        assert_eq!(a.manhattan_distance(b), 513);
+        assert_eq!(a.chebyshev_distance(b), 263);
+        assert_eq!(a.euclidean_distance_squared(b), 100_427);
+    }
+}
@@ -0,0 +1,213 @@
+use std::{
+    borrow::Borrow,
+    collections::{HashMap, VecDeque},
+    fmt::Display,
+    hash::Hash,
+};
+
+use super::debugging::log_verbose;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub entries: usize,
+}
+
+impl MemoStats {
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            return 0.0;
+        }
+
+        self.hits as f64 / lookups as f64
+    }
+}
+
+impl Display for MemoStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate), {} entries, {} evictions",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0,
+            self.entries,
+            self.evictions
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    // Keys in insertion order, so the oldest entry is evicted first
+    order: VecDeque<K>,
+    size_limit: Option<usize>,
+    stats: MemoStats,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            cache: HashMap::new(),
+            order: VecDeque::new(),
+            size_limit: None,
+            stats: MemoStats::default(),
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_size_limit(size_limit: usize) -> Self {
+        Self {
+            size_limit: Some(size_limit),
+            ..Self::default()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.order.clear();
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        MemoStats {
+            entries: self.cache.len(),
+            ..self.stats
+        }
+    }
+
+    // Prints the statistics when the CLI runs with --verbose
+    pub fn report_stats(&self, name: &str) {
+        log_verbose(format!("{}: {}", name, self.stats()));
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.cache.get(key)
+    }
+
+    // The compute function receives the memo itself so recursive calls share the cache.
+    pub fn get_or_compute<F>(&mut self, key: K, compute: F) -> V
+    where
+        F: FnOnce(&mut Self) -> V,
+    {
+        if let Some(value) = self.cache.get(&key) {
+            self.stats.hits += 1;
+            return value.clone();
+        }
+
+        self.stats.misses += 1;
+        let value = compute(self);
+        self.insert(key, value.clone());
+
+        value
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        if self.size_limit == Some(0) {
+            return;
+        }
+
+        if let Some(existing) = self.cache.get_mut(&key) {
+            *existing = value;
+            return;
+        }
+
+        let is_full = self
+            .size_limit
+            .is_some_and(|limit| self.cache.len() >= limit);
+        if is_full {
+            if let Some(evicted) = self.order.pop_front() {
+                self.cache.remove(&evicted);
+                self.stats.evictions += 1;
+            }
+        }
+
+        self.order.push_back(key.clone());
+        self.cache.insert(key, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Memo;
+
+    fn fibonacci(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+        if n < 2 {
+            return n;
+        }
+
+        memo.get_or_compute(n, |memo| fibonacci(n - 1, memo) + fibonacci(n - 2, memo))
+    }
+
+    #[test]
+    fn recursive_calls_share_cache() {
+        let mut memo = Memo::new();
+        assert_eq!(fibonacci(90, &mut memo), 2880067194370816120);
+
+        let stats = memo.stats();
+        assert_eq!(stats.misses, 89);
+        assert_eq!(stats.hits, 87);
+        assert_eq!(stats.entries, 89);
+        assert_eq!(memo.get(&10), Some(&55));
+    }
+
+    #[test]
+    fn size_limit_evicts_entries() {
+        let mut memo = Memo::with_size_limit(2);
+        for key in 0..5 {
+            memo.get_or_compute(key, |_| key * 2);
+        }
+
+        let stats = memo.stats();
+        assert_eq!(stats.entries, 2);
+        assert_eq!(stats.evictions, 3);
+        assert_eq!(stats.misses, 5);
+        assert_eq!(stats.hit_rate(), 0.0);
+    }
+
+    #[test]
+    fn size_limit_evicts_oldest_entry() {
+        let mut memo = Memo::with_size_limit(2);
+        memo.get_or_compute(0, |_| 0);
+        memo.get_or_compute(1, |_| 1);
+        memo.get_or_compute(0, |_| 0);
+        memo.get_or_compute(2, |_| 2);
+
+        assert_eq!(memo.get(&0), None);
+        assert_eq!(memo.get(&1), Some(&1));
+        assert_eq!(memo.get(&2), Some(&2));
+        assert_eq!(memo.stats().evictions, 1);
+    }
+
+    #[test]
+    fn display_stats() {
+        let mut memo = Memo::new();
+        memo.get_or_compute("a", |_| 1);
+        memo.get_or_compute("a", |_| 1);
+
+        assert_eq!(
+            memo.stats().to_string(),
+            "1 hits, 1 misses (50.0% hit rate), 1 entries, 0 evictions"
+        );
+    }
+}
@@ -1,3 +1,14 @@
+pub mod cycle;
 pub mod debugging;
+pub mod dsu;
+pub mod geometry;
+pub mod graph;
+pub mod intervals;
+pub mod linalg;
 pub mod math_2d;
+pub mod math_3d;
+pub mod memo;
+pub mod number_theory;
+pub mod ocr;
 pub mod parsing;
+pub mod vm;
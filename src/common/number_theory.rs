@@ -0,0 +1,241 @@
+pub fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+// `lcm`, `lcm_of` and `concat_digits` overflow for large inputs, use the checked variants
+// when the inputs are not bounded by the puzzle
+pub fn lcm(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+
+    a / gcd(a, b) * b
+}
+
+pub fn checked_lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+pub fn gcd_of<I: IntoIterator<Item = u64>>(values: I) -> u64 {
+    values.into_iter().fold(0, gcd)
+}
+
+pub fn lcm_of<I: IntoIterator<Item = u64>>(values: I) -> u64 {
+    values.into_iter().fold(1, lcm)
+}
+
+pub fn checked_lcm_of<I: IntoIterator<Item = u64>>(values: I) -> Option<u64> {
+    values.into_iter().try_fold(1, checked_lcm)
+}
+
+// The Bezout coefficients are bounded by the inputs, so this only overflows for i128::MIN
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        return (a, 1, 0);
+    }
+
+    let (gcd, x, y) = extended_gcd(b, a % b);
+    (gcd, y, x - (a / b) * y)
+}
+
+pub fn modulo(value: i64, modulus: u64) -> u64 {
+    let modulus = i128::from(modulus);
+    i128::from(value).rem_euclid(modulus) as u64
+}
+
+pub fn mod_inverse(value: u64, modulus: u64) -> Option<u64> {
+    if modulus == 0 {
+        return None;
+    }
+
+    let (gcd, inverse, _) = extended_gcd(i128::from(value), i128::from(modulus));
+    if gcd != 1 {
+        return None;
+    }
+
+    Some(inverse.rem_euclid(i128::from(modulus)) as u64)
+}
+
+pub fn mod_pow(base: u64, exponent: u64, modulus: u64) -> u64 {
+    if modulus == 1 {
+        return 0;
+    }
+
+    let modulus = u128::from(modulus);
+    let mut base = u128::from(base) % modulus;
+    let mut exponent = exponent;
+    let mut result = 1;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+
+    result as u64
+}
+
+// Returns `None` if the congruences are inconsistent or the combined modulus exceeds u64
+pub fn crt(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+    let mut residue: i128 = 0;
+    let mut modulus: i128 = 1;
+
+    for &(other_residue, other_modulus) in congruences {
+        if other_modulus == 0 {
+            return None;
+        }
+
+        let other_residue = i128::from(other_residue % other_modulus);
+        let other_modulus = i128::from(other_modulus);
+
+        let (gcd, inverse, _) = extended_gcd(modulus, other_modulus);
+        let difference = other_residue - residue;
+        if difference % gcd != 0 {
+            return None;
+        }
+
+        // Both factors are below `reduced_modulus`, so their product fits once the combined
+        // modulus is known to fit into u64
+        let reduced_modulus = other_modulus / gcd;
+        let combined_modulus = u64::try_from(modulus.checked_mul(reduced_modulus)?).ok()?;
+        let factor = (difference / gcd % reduced_modulus)
+            .checked_mul(inverse % reduced_modulus)?
+            .rem_euclid(reduced_modulus);
+
+        residue = residue
+            .checked_add(modulus.checked_mul(factor)?)?
+            .rem_euclid(i128::from(combined_modulus));
+        modulus = i128::from(combined_modulus);
+    }
+
+    Some((u64::try_from(residue).ok()?, u64::try_from(modulus).ok()?))
+}
+
+pub fn count_digits(value: u64) -> u32 {
+    value.checked_ilog10().unwrap_or(0) + 1
+}
+
+pub fn digits(value: u64) -> Vec<u8> {
+    let mut digits = Vec::with_capacity(count_digits(value) as usize);
+    let mut remaining = value;
+    loop {
+        digits.push((remaining % 10) as u8);
+        remaining /= 10;
+        if remaining == 0 {
+            break;
+        }
+    }
+
+    digits.reverse();
+    digits
+}
+
+pub fn split_digits(value: u64, low_digit_count: u32) -> (u64, u64) {
+    match 10_u64.checked_pow(low_digit_count) {
+        Some(divisor) => (value / divisor, value % divisor),
+        None => (0, value),
+    }
+}
+
+pub fn concat_digits(a: u64, b: u64) -> u64 {
+    a * 10_u64.pow(count_digits(b)) + b
+}
+
+pub fn checked_concat_digits(a: u64, b: u64) -> Option<u64> {
+    a.checked_mul(10_u64.checked_pow(count_digits(b))?)?
+        .checked_add(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        checked_concat_digits, checked_lcm, checked_lcm_of, concat_digits, count_digits, crt,
+        digits, extended_gcd, gcd, gcd_of, lcm, lcm_of, mod_inverse, mod_pow, modulo, split_digits,
+    };
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(48, 18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        assert_eq!(lcm_of([2, 3, 4, 5]), 60);
+        assert_eq!(gcd_of([12, 18, 30]), 6);
+    }
+
+    #[test]
+    fn checked_lcm_detects_overflow() {
+        assert_eq!(checked_lcm(6, 10), Some(30));
+        assert_eq!(checked_lcm(u64::MAX, 2), None);
+        assert_eq!(checked_lcm_of([u64::MAX - 1, 3, 5]), None);
+    }
+
+    #[test]
+    fn extended_gcd_finds_bezout_coefficients() {
+        let (gcd, x, y) = extended_gcd(240, 46);
+        assert_eq!(gcd, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+    }
+
+    #[test]
+    fn modular_arithmetic() {
+        assert_eq!(modulo(-3, 11), 8);
+        assert_eq!(modulo(-25, 11), 8);
+        assert_eq!(modulo(i64::MIN, 7), 6);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(4, 8), None);
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(u64::MAX, u64::MAX, u64::MAX - 1), 1);
+    }
+
+    #[test]
+    fn crt_with_coprime_moduli() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+    }
+
+    #[test]
+    fn crt_with_non_coprime_moduli() {
+        assert_eq!(crt(&[(3, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt(&[(0, 4), (1, 6)]), None);
+    }
+
+    #[test]
+    fn crt_detects_overflow() {
+        assert_eq!(crt(&[(1, u64::MAX), (1, u64::MAX - 1)]), None);
+        assert_eq!(
+            crt(&[(3, 4_294_967_291), (5, 4_294_967_279)]),
+            Some((3_074_457_330_585_873_083, 18_446_743_979_220_271_189))
+        );
+    }
+
+    #[test]
+    fn digit_utilities() {
+        assert_eq!(count_digits(0), 1);
+        assert_eq!(count_digits(9), 1);
+        assert_eq!(count_digits(10), 2);
+        assert_eq!(count_digits(u64::MAX), 20);
+        assert_eq!(digits(1024), [1, 0, 2, 4]);
+        assert_eq!(digits(0), [0]);
+        assert_eq!(split_digits(253000, 3), (253, 0));
+        assert_eq!(split_digits(17, 25), (0, 17));
+        assert_eq!(concat_digits(15, 6), 156);
+        assert_eq!(concat_digits(12, 0), 120);
+    }
+
+    #[test]
+    fn checked_concat_digits_detects_overflow() {
+        assert_eq!(checked_concat_digits(15, 6), Some(156));
+        assert_eq!(checked_concat_digits(u64::MAX / 10, 99), None);
+        assert_eq!(checked_concat_digits(1, u64::MAX), None);
+    }
+}
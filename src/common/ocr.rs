@@ -0,0 +1,293 @@
+use anyhow::{bail, Result};
+use itertools::Itertools;
+
+use crate::common::math_2d::{Grid, UPoint};
+
+const GLYPHS_4X6: &[(char, [&str; 6])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+const GLYPHS_6X10: &[(char, [&str; 10])] = &[
+    (
+        'A',
+        [
+            "..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'B',
+        [
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#",
+            "#....#", "#####.",
+        ],
+    ),
+    (
+        'C',
+        [
+            ".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+            "#....#", ".####.",
+        ],
+    ),
+    (
+        'E',
+        [
+            "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+    (
+        'F',
+        [
+            "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "#.....",
+        ],
+    ),
+    (
+        'G',
+        [
+            ".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#",
+            "#...##", ".###.#",
+        ],
+    ),
+    (
+        'H',
+        [
+            "#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'J',
+        [
+            "...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.",
+            "#...#.", ".###..",
+        ],
+    ),
+    (
+        'K',
+        [
+            "#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..",
+            "#...#.", "#....#",
+        ],
+    ),
+    (
+        'L',
+        [
+            "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+    (
+        'N',
+        [
+            "#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##",
+            "#...##", "#....#",
+        ],
+    ),
+    (
+        'P',
+        [
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "#.....",
+        ],
+    ),
+    (
+        'R',
+        [
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'X',
+        [
+            "#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'Z',
+        [
+            "######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+];
+
+pub fn recognize(grid: &Grid<bool>) -> Result<String> {
+    let dim = grid.udims();
+    let alphabet = match dim.height {
+        6 => GLYPHS_4X6
+            .iter()
+            .map(|(c, rows)| (*c, rows.to_vec()))
+            .collect_vec(),
+        10 => GLYPHS_6X10
+            .iter()
+            .map(|(c, rows)| (*c, rows.to_vec()))
+            .collect_vec(),
+        height => bail!("Letters must be 6 or 10 rows high, got {}", height),
+    };
+
+    let is_lit = |x: usize, y: usize| grid.get(UPoint::new(x, y)).copied().unwrap_or(false);
+    let is_blank_column = |x: usize| (0..dim.height).all(|y| !is_lit(x, y));
+
+    let mut letters = String::new();
+    let mut unrecognized = Vec::new();
+    let mut x = 0;
+    while x < dim.width {
+        if is_blank_column(x) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < dim.width && !is_blank_column(x) {
+            x += 1;
+        }
+
+        let glyph = (0..dim.height)
+            .map(|y| {
+                (start..x)
+                    .map(|x| if is_lit(x, y) { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect_vec();
+
+        match alphabet
+            .iter()
+            .find(|(_, rows)| trim_blank_columns(rows) == glyph)
+        {
+            Some((letter, _)) => letters.push(*letter),
+            None => unrecognized.push((start, glyph)),
+        }
+    }
+
+    if !unrecognized.is_empty() {
+        let glyphs = unrecognized
+            .iter()
+            .map(|(column, glyph)| format!("column {}:\n{}", column, glyph.join("\n")))
+            .join("\n");
+        bail!("Unrecognized glyphs at {}", glyphs);
+    }
+
+    Ok(letters)
+}
+
+// Lit pixels are '#', unlit pixels may be '.' or ' ' and rows may be of different length
+pub fn recognize_str(art: &str) -> Result<String> {
+    let width = art.lines().map(|line| line.chars().count()).max();
+    let values = art
+        .lines()
+        .map(|line| {
+            let mut row = line
+                .chars()
+                .map(|c| match c {
+                    '#' => Ok(true),
+                    '.' | ' ' => Ok(false),
+                    _ => bail!("Unexpected pixel {:?} in letter art", c),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            row.resize(width.unwrap_or(0), false);
+            Ok(row)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    recognize(&Grid::from_raw_values(values)?)
+}
+
+fn trim_blank_columns(rows: &[&str]) -> Vec<String> {
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let is_lit = |x: usize| rows.iter().any(|row| row.as_bytes().get(x) == Some(&b'#'));
+    let start = (0..width).find(|&x| is_lit(x)).unwrap_or(0);
+    let end = (0..width).rfind(|&x| is_lit(x)).map_or(0, |x| x + 1);
+
+    rows.iter()
+        .map(|row| row.get(start..end).unwrap_or_default().to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{recognize_str, GLYPHS_6X10};
+
+    #[test]
+    fn recognize_small_letters() {
+        let art = "\
+####.###..#..#.###..#..#.####..##..#..#.
+#....#..#.#..#.#..#.#..#....#.#..#.#..#.
+###..###..#..#.#..#.####...#..#....####.
+#....#..#.#..#.###..#..#..#...#....#..#.
+#....#..#.#..#.#.#..#..#.#....#..#.#..#.
+#....###...##..#..#.#..#.####..##..#..#.";
+
+        assert_eq!(recognize_str(art).unwrap(), "FBURHZCH");
+    }
+
+    #[test]
+    fn recognize_letters_of_different_width() {
+        let art = "\
+###..#...#
+.#...#...#
+.#....#.#
+.#.....#
+.#.....#
+###....#";
+
+        assert_eq!(recognize_str(art).unwrap(), "IY");
+    }
+
+    #[test]
+    fn recognize_large_letters() {
+        let rows = (0..10)
+            .map(|y| {
+                ['N', 'X']
+                    .iter()
+                    .map(|c| GLYPHS_6X10.iter().find(|(l, _)| l == c).unwrap().1[y])
+                    .collect::<Vec<_>>()
+                    .join("..")
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(recognize_str(&rows.join("\n")).unwrap(), "NX");
+    }
+
+    #[test]
+    fn report_unrecognized_glyphs() {
+        let art = "\
+.##...#
+#..#..#
+#..#..#
+####..#
+#..#..#
+#..#..#";
+
+        let err = recognize_str(art).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unrecognized glyphs at column 6:\n#\n#\n#\n#\n#\n#"
+        );
+    }
+
+    #[test]
+    fn reject_unsupported_height() {
+        assert!(recognize_str("#\n#").is_err());
+    }
+}
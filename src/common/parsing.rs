@@ -1,6 +1,17 @@
+use std::{error::Error, str::FromStr};
+
 use crate::common::math_2d::Grid;
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
+
+pub fn normalize_input(input: &str) -> String {
+    input
+        .strip_prefix('\u{feff}')
+        .unwrap_or(input)
+        .replace("\r\n", "\n")
+        .trim_end_matches('\n')
+        .to_string()
+}
 
 pub fn parse_grid<T, F>(input: &str, mapper: F) -> Result<Grid<T>>
 where
@@ -20,3 +31,246 @@ where
 
     Grid::from_raw_values(values)
 }
+
+pub fn parse_lines<T, F>(input: &str, mapper: F) -> Result<Vec<T>>
+where
+    F: Fn(&str) -> Result<T>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| mapper(line).with_context(|| format!("Failed parsing line {}", idx + 1)))
+        .collect()
+}
+
+pub fn split_blocks(input: &str) -> Vec<&str> {
+    let mut blocks = Vec::new();
+    let mut block_start = None;
+    let mut block_end = 0;
+    let mut offset = 0;
+
+    for line in input.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\n', '\r']);
+        if content.is_empty() {
+            if let Some(start) = block_start.take() {
+                blocks.push(&input[start..block_end]);
+            }
+        } else {
+            block_start.get_or_insert(offset);
+            block_end = offset + content.len();
+        }
+        offset += line.len();
+    }
+
+    if let Some(start) = block_start {
+        blocks.push(&input[start..block_end]);
+    }
+
+    blocks
+}
+
+pub fn parse_integers<T>(line: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    let bytes = line.as_bytes();
+    let mut values = Vec::new();
+    let mut idx = 0;
+
+    while idx < bytes.len() {
+        let is_sign = bytes[idx] == b'-'
+            && bytes.get(idx + 1).is_some_and(u8::is_ascii_digit)
+            && (idx == 0 || !bytes[idx - 1].is_ascii_alphanumeric());
+
+        if !is_sign && !bytes[idx].is_ascii_digit() {
+            idx += 1;
+            continue;
+        }
+
+        let start = idx;
+        idx += 1;
+        while idx < bytes.len() && bytes[idx].is_ascii_digit() {
+            idx += 1;
+        }
+
+        let token = &line[start..idx];
+        let value = token.parse::<T>().with_context(|| {
+            format!("Failed parsing integer '{}' at column {}", token, start + 1)
+        })?;
+        values.push(value);
+    }
+
+    Ok(values)
+}
+
+pub fn parse_key_values<T>(line: &str) -> Result<(&str, Vec<T>)>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    let (raw_key, values) = line
+        .split_once(':')
+        .ok_or_else(|| anyhow!("Expected 'key: values' but found no ':' in '{}'", line))?;
+
+    let key = raw_key.trim();
+    if key.is_empty() {
+        bail!("Missing key before ':' in '{}'", line);
+    }
+
+    let mut parsed_values = Vec::new();
+    let mut column = raw_key.len() + 1;
+    for token in values.split_inclusive([' ', ',', '\t']) {
+        let value = token.trim_end_matches([' ', ',', '\t']);
+        if !value.is_empty() {
+            let parsed = value.parse::<T>().with_context(|| {
+                format!("Failed parsing value '{}' at column {}", value, column + 1)
+            })?;
+            parsed_values.push(parsed);
+        }
+        column += token.len();
+    }
+
+    Ok((key, parsed_values))
+}
+
+pub fn split_fixed_columns<'a>(line: &'a str, widths: &[usize]) -> Result<Vec<&'a str>> {
+    let mut columns = Vec::with_capacity(widths.len());
+    let mut start = 0;
+
+    for (idx, width) in widths.iter().enumerate() {
+        let end = (start + width).min(line.len());
+        if start > line.len() {
+            bail!(
+                "Line ends before column {} starting at column {}",
+                idx + 1,
+                start + 1
+            );
+        }
+        if !line.is_char_boundary(start) || !line.is_char_boundary(end) {
+            bail!(
+                "Column {} at column {} is not aligned to a character",
+                idx + 1,
+                start + 1
+            );
+        }
+
+        columns.push(&line[start..end]);
+        start += width;
+    }
+
+    Ok(columns)
+}
+
+pub fn split_repeated_columns(line: &str, width: usize, gap: usize) -> Result<Vec<&str>> {
+    if width == 0 {
+        bail!("Column width must be greater than 0");
+    }
+
+    let count = (line.len() + gap).div_ceil(width + gap);
+    let widths = (0..count)
+        .flat_map(|_| [width, gap])
+        .take((count * 2).saturating_sub(1))
+        .collect::<Vec<_>>();
+
+    let columns = split_fixed_columns(line, &widths)?
+        .into_iter()
+        .step_by(2)
+        .collect();
+
+    Ok(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        normalize_input, parse_integers, parse_key_values, parse_lines, split_blocks,
+        split_fixed_columns, split_repeated_columns,
+    };
+
+    #[test]
+    fn normalize_input_unifies_line_endings() {
+        let expected = "a\nb\n\nc";
+
+        assert_eq!(normalize_input("a\nb\n\nc\n"), expected);
+        assert_eq!(normalize_input("a\r\nb\r\n\r\nc\r\n\r\n"), expected);
+        assert_eq!(normalize_input("\u{feff}a\r\nb\n\nc"), expected);
+    }
+
+    #[test]
+    fn split_blocks_ignores_line_endings() {
+        let lf = "a\nb\n\nc\n\n\nd\n";
+        let crlf = "a\r\nb\r\n\r\nc\r\n\r\n\r\nd\r\n";
+
+        assert_eq!(split_blocks(lf), ["a\nb", "c", "d"]);
+        assert_eq!(split_blocks(crlf), ["a\r\nb", "c", "d"]);
+    }
+
+    #[test]
+    fn split_blocks_skips_surrounding_blank_lines() {
+        assert_eq!(split_blocks("\n\nabc\n\n"), ["abc"]);
+        assert!(split_blocks("").is_empty());
+    }
+
+    #[test]
+    fn parse_integers_handles_signs() {
+        let values = parse_integers::<i32>("p=0,4 v=3,-3").unwrap();
+        assert_eq!(values, [0, 4, 3, -3]);
+
+        let values = parse_integers::<i64>("Button A: X+94, Y+34").unwrap();
+        assert_eq!(values, [94, 34]);
+
+        let values = parse_integers::<u32>("2-4,6-8").unwrap();
+        assert_eq!(values, [2, 4, 6, 8]);
+    }
+
+    #[test]
+    fn parse_integers_reports_column() {
+        let err = parse_integers::<u8>("a 12 300").unwrap_err();
+        assert_eq!(err.to_string(), "Failed parsing integer '300' at column 6");
+    }
+
+    #[test]
+    fn parse_lines_reports_line() {
+        let err = parse_lines("1\n2\nx", |l| Ok(l.parse::<u8>()?)).unwrap_err();
+        assert_eq!(err.to_string(), "Failed parsing line 3");
+    }
+
+    #[test]
+    fn parse_key_values_splits_key() {
+        let (key, values) = parse_key_values::<u64>("190: 10 19").unwrap();
+        assert_eq!(key, "190");
+        assert_eq!(values, [10, 19]);
+
+        let (key, values) = parse_key_values::<String>("aaa: you, hhh").unwrap();
+        assert_eq!(key, "aaa");
+        assert_eq!(values, ["you", "hhh"]);
+    }
+
+    #[test]
+    fn parse_key_values_reports_column() {
+        let err = parse_key_values::<u8>("seeds: 1 x").unwrap_err();
+        assert_eq!(err.to_string(), "Failed parsing value 'x' at column 10");
+        assert!(parse_key_values::<u8>("seeds 1 2").is_err());
+    }
+
+    #[test]
+    fn split_fixed_columns_by_width() {
+        let columns = split_fixed_columns("123 45 6", &[3, 1, 2, 1, 1]).unwrap();
+        assert_eq!(columns, ["123", " ", "45", " ", "6"]);
+
+        let columns = split_fixed_columns("12", &[2, 1]).unwrap();
+        assert_eq!(columns, ["12", ""]);
+
+        assert!(split_fixed_columns("12", &[3, 1, 1]).is_err());
+    }
+
+    #[test]
+    fn split_repeated_columns_with_gap() {
+        let columns = split_repeated_columns("    [D]    ", 3, 1).unwrap();
+        assert_eq!(columns, ["   ", "[D]", "   "]);
+
+        let columns = split_repeated_columns("[Z] [M] [P]", 3, 1).unwrap();
+        assert_eq!(columns, ["[Z]", "[M]", "[P]"]);
+    }
+}
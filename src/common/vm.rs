@@ -0,0 +1,377 @@
+use std::{
+    collections::HashSet,
+    fmt::{Debug, Display},
+};
+
+use anyhow::{bail, Result};
+use itertools::Itertools;
+
+pub trait Machine {
+    type Word: Copy;
+    type Instruction: Copy + Debug + Display;
+    type Registers: Clone + Debug + Display;
+
+    // Returns the instruction at `pc` together with the number of words it occupies
+    fn decode(program: &[Self::Word], pc: usize) -> Result<(Self::Instruction, usize)>;
+
+    fn cycles(_instruction: &Self::Instruction) -> u64 {
+        1
+    }
+
+    // Returns the new pc for jumps, `None` continues with the next instruction
+    fn execute(&mut self, instruction: Self::Instruction) -> Result<Option<usize>>;
+
+    fn registers(&self) -> &Self::Registers;
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RegisterFile<T, const N: usize> {
+    names: [&'static str; N],
+    values: [T; N],
+}
+
+impl<T: Copy, const N: usize> RegisterFile<T, N> {
+    pub fn new(names: [&'static str; N], values: [T; N]) -> Self {
+        Self { names, values }
+    }
+
+    pub fn get(&self, register: usize) -> T {
+        self.values[register]
+    }
+
+    pub fn set(&mut self, register: usize, value: T) {
+        self.values[register] = value;
+    }
+
+    pub fn get_mut(&mut self, register: usize) -> &mut T {
+        &mut self.values[register]
+    }
+
+    pub fn names(&self) -> &[&'static str; N] {
+        &self.names
+    }
+
+    pub fn values(&self) -> &[T; N] {
+        &self.values
+    }
+}
+
+impl<T: Display, const N: usize> Display for RegisterFile<T, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let registers = self
+            .names
+            .iter()
+            .zip(self.values.iter())
+            .map(|(name, value)| format!("{}={}", name, value))
+            .join(" ");
+        write!(f, "{}", registers)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    Halted,
+    Breakpoint(usize),
+    StepLimit,
+}
+
+#[derive(Debug, Clone)]
+pub struct TraceEntry<M: Machine> {
+    pub pc: usize,
+    pub cycle: u64,
+    pub instruction: M::Instruction,
+    pub registers: M::Registers,
+}
+
+impl<M: Machine> Display for TraceEntry<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "pc={:02} {:<6} {}",
+            self.pc,
+            self.instruction.to_string(),
+            self.registers
+        )
+    }
+}
+
+pub struct Vm<M: Machine> {
+    machine: M,
+    program: Vec<M::Word>,
+    pc: usize,
+    cycle: u64,
+    steps: usize,
+    step_limit: Option<usize>,
+    breakpoints: HashSet<usize>,
+    trace: Option<Vec<TraceEntry<M>>>,
+}
+
+impl<M: Machine> Vm<M> {
+    pub fn new(machine: M, program: Vec<M::Word>) -> Self {
+        Self {
+            machine,
+            program,
+            pc: 0,
+            cycle: 0,
+            steps: 0,
+            step_limit: None,
+            breakpoints: HashSet::new(),
+            trace: None,
+        }
+    }
+
+    pub fn with_step_limit(mut self, step_limit: usize) -> Self {
+        self.step_limit = Some(step_limit);
+        self
+    }
+
+    pub fn machine(&self) -> &M {
+        &self.machine
+    }
+
+    pub fn machine_mut(&mut self) -> &mut M {
+        &mut self.machine
+    }
+
+    pub fn program(&self) -> &[M::Word] {
+        &self.program
+    }
+
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    pub fn cycle(&self) -> u64 {
+        self.cycle
+    }
+
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.pc >= self.program.len()
+    }
+
+    pub fn enable_trace(&mut self) {
+        self.trace.get_or_insert_with(Vec::new);
+    }
+
+    pub fn trace(&self) -> &[TraceEntry<M>] {
+        self.trace.as_deref().unwrap_or_default()
+    }
+
+    pub fn add_breakpoint(&mut self, pc: usize) {
+        self.breakpoints.insert(pc);
+    }
+
+    pub fn disassemble(&self) -> Result<Vec<(usize, M::Instruction)>> {
+        let mut instructions = Vec::new();
+        let mut pc = 0;
+        while pc < self.program.len() {
+            let (instruction, size) = M::decode(&self.program, pc)?;
+            instructions.push((pc, instruction));
+            pc += size;
+        }
+
+        Ok(instructions)
+    }
+
+    pub fn run(&mut self) -> Result<()> {
+        self.run_with_hook(|_, _| {})
+    }
+
+    // The hook is called once per cycle while an instruction is executing, before its effects
+    // are applied
+    pub fn run_with_hook(&mut self, mut hook: impl FnMut(u64, &M)) -> Result<()> {
+        while self.step_with_hook(&mut hook)?.is_some() {}
+
+        Ok(())
+    }
+
+    // Always executes at least one instruction, so resuming from a breakpoint makes progress
+    pub fn resume(&mut self) -> Result<StopReason> {
+        loop {
+            if self.step_limit_reached() {
+                return Ok(StopReason::StepLimit);
+            }
+
+            if self.step()?.is_none() || self.is_halted() {
+                return Ok(StopReason::Halted);
+            }
+
+            if self.breakpoints.contains(&self.pc) {
+                return Ok(StopReason::Breakpoint(self.pc));
+            }
+        }
+    }
+
+    pub fn step(&mut self) -> Result<Option<M::Instruction>> {
+        self.step_with_hook(|_, _| {})
+    }
+
+    pub fn step_with_hook(
+        &mut self,
+        mut hook: impl FnMut(u64, &M),
+    ) -> Result<Option<M::Instruction>> {
+        if self.is_halted() {
+            return Ok(None);
+        }
+
+        if let Some(limit) = self.step_limit {
+            if self.steps >= limit {
+                bail!("Step limit of {} reached at pc {}", limit, self.pc);
+            }
+        }
+
+        let (instruction, size) = M::decode(&self.program, self.pc)?;
+        if let Some(trace) = self.trace.as_mut() {
+            trace.push(TraceEntry {
+                pc: self.pc,
+                cycle: self.cycle,
+                instruction,
+                registers: self.machine.registers().clone(),
+            });
+        }
+
+        for _ in 0..M::cycles(&instruction) {
+            self.cycle += 1;
+            hook(self.cycle, &self.machine);
+        }
+
+        self.pc = match self.machine.execute(instruction)? {
+            Some(target) => target,
+            None => self.pc + size,
+        };
+        self.steps += 1;
+
+        Ok(Some(instruction))
+    }
+
+    fn step_limit_reached(&self) -> bool {
+        self.step_limit.is_some_and(|limit| self.steps >= limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Display;
+
+    use anyhow::{bail, Result};
+
+    use super::{Machine, RegisterFile, StopReason, Vm};
+
+    #[derive(Debug, Clone, Copy)]
+    enum Op {
+        Inc,
+        Mul(i64),
+        Jump(usize),
+    }
+
+    impl Display for Op {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Op::Inc => write!(f, "inc"),
+                Op::Mul(value) => write!(f, "mul {}", value),
+                Op::Jump(target) => write!(f, "jmp {}", target),
+            }
+        }
+    }
+
+    struct Accumulator {
+        registers: RegisterFile<i64, 1>,
+    }
+
+    impl Machine for Accumulator {
+        type Word = i64;
+        type Instruction = Op;
+        type Registers = RegisterFile<i64, 1>;
+
+        fn decode(program: &[i64], pc: usize) -> Result<(Op, usize)> {
+            match program[pc] {
+                0 => Ok((Op::Inc, 1)),
+                1 => Ok((Op::Mul(program[pc + 1]), 2)),
+                2 => Ok((Op::Jump(program[pc + 1] as usize), 2)),
+                value => bail!("Unknown opcode {}", value),
+            }
+        }
+
+        fn cycles(instruction: &Op) -> u64 {
+            match instruction {
+                Op::Mul(_) => 3,
+                _ => 1,
+            }
+        }
+
+        fn execute(&mut self, instruction: Op) -> Result<Option<usize>> {
+            match instruction {
+                Op::Inc => *self.registers.get_mut(0) += 1,
+                Op::Mul(value) => *self.registers.get_mut(0) *= value,
+                Op::Jump(target) => return Ok(Some(target)),
+            }
+
+            Ok(None)
+        }
+
+        fn registers(&self) -> &RegisterFile<i64, 1> {
+            &self.registers
+        }
+    }
+
+    fn vm(program: Vec<i64>) -> Vm<Accumulator> {
+        let machine = Accumulator {
+            registers: RegisterFile::new(["acc"], [0]),
+        };
+        Vm::new(machine, program)
+    }
+
+    #[test]
+    fn hooks_see_state_before_instruction_completes() {
+        let mut vm = vm(vec![0, 1, 5, 0]);
+        let mut observed = Vec::new();
+        vm.run_with_hook(|cycle, m| observed.push((cycle, m.registers.get(0))))
+            .unwrap();
+
+        assert_eq!(observed, [(1, 0), (2, 1), (3, 1), (4, 1), (5, 5)]);
+        assert_eq!(vm.machine().registers.get(0), 6);
+        assert_eq!(vm.cycle(), 5);
+        assert_eq!(vm.steps(), 3);
+    }
+
+    #[test]
+    fn trace_and_disassembly() {
+        let mut vm = vm(vec![0, 1, 3]);
+        vm.enable_trace();
+        vm.run().unwrap();
+
+        let trace = vm.trace().iter().map(|e| e.to_string()).collect::<Vec<_>>();
+        assert_eq!(trace, ["pc=00 inc    acc=0", "pc=01 mul 3  acc=1"]);
+        assert_eq!(vm.trace()[1].cycle, 1);
+
+        let listing = vm.disassemble().unwrap();
+        assert_eq!(listing.len(), 2);
+        assert_eq!(listing[1].0, 1);
+    }
+
+    #[test]
+    fn step_limit_stops_endless_loops() {
+        let mut vm = vm(vec![0, 2, 0]).with_step_limit(10);
+        let err = vm.run().unwrap_err();
+        assert_eq!(err.to_string(), "Step limit of 10 reached at pc 0");
+
+        let mut vm = vm.with_step_limit(11);
+        assert_eq!(vm.resume().unwrap(), StopReason::StepLimit);
+        assert_eq!(vm.machine().registers.get(0), 6);
+    }
+
+    #[test]
+    fn breakpoints() {
+        let mut vm = vm(vec![0, 0, 0]);
+        vm.add_breakpoint(2);
+
+        assert_eq!(vm.resume().unwrap(), StopReason::Breakpoint(2));
+        assert_eq!(vm.machine().registers.get(0), 2);
+        assert_eq!(vm.resume().unwrap(), StopReason::Halted);
+        assert!(vm.step().unwrap().is_none());
+    }
+}
@@ -1,4 +1,8 @@
-use std::{collections::HashMap, error::Error};
+use std::{
+    collections::HashMap,
+    error::Error,
+    path::{Path, PathBuf},
+};
 
 use linkme::distributed_slice;
 
@@ -15,11 +19,47 @@ pub struct ExecutionArgs {
     pub year: u16,
     pub day: u8,
     pub part: u8,
+    pub verbose: bool,
+}
+
+pub enum ToolArgs {
+    ChronoDisasm {
+        input: PathBuf,
+        trace: bool,
+        breakpoints: Vec<usize>,
+    },
+    CamelCards {
+        input: PathBuf,
+        wildcards: String,
+    },
+    SupplyStacks {
+        input: PathBuf,
+        model: u32,
+    },
+    Filesystem {
+        input: PathBuf,
+        path: String,
+    },
+    RopeBridge {
+        input: PathBuf,
+        knots: usize,
+    },
+}
+
+pub enum Command {
+    Solve(ExecutionArgs),
+    Tool(ToolArgs),
 }
 
 pub fn run() -> MyResult<()> {
-    let args = cli::parse_args()?;
+    match cli::parse_args()? {
+        Command::Solve(args) => solve(args),
+        Command::Tool(args) => run_tool(args),
+    }
+}
 
+fn solve(args: ExecutionArgs) -> MyResult<()> {
+    common::debugging::set_verbose(args.verbose);
     let solvers = collect_solver_map()?;
 
     let date = SolverDate {
@@ -35,7 +75,7 @@ pub fn run() -> MyResult<()> {
         .into());
     };
 
-    let solution = (solver.func)(solver.input)?;
+    let solution = execute_solver(solver.func, solver.input)?;
 
     println!(
         "Solution for year {} day {:02} part {}: {}",
@@ -45,6 +85,51 @@ pub fn run() -> MyResult<()> {
     Ok(())
 }
 
+fn run_tool(args: ToolArgs) -> MyResult<()> {
+    match args {
+        ToolArgs::ChronoDisasm {
+            input,
+            trace,
+            breakpoints,
+        } => {
+            let input = read_tool_input(&input)?;
+            println!(
+                "{}",
+                year_2024::day_17::chrono_disasm(&input, trace, &breakpoints)?
+            );
+        }
+        ToolArgs::CamelCards { input, wildcards } => {
+            let input = read_tool_input(&input)?;
+            println!("{}", year_2023::day_07::camel_cards(&input, &wildcards)?);
+        }
+        ToolArgs::SupplyStacks { input, model } => {
+            let input = read_tool_input(&input)?;
+            println!("{}", year_2022::day_05::supply_stacks(&input, model)?);
+        }
+        ToolArgs::Filesystem { input, path } => {
+            let input = read_tool_input(&input)?;
+            println!("{}", year_2022::day_07::filesystem(&input, &path)?);
+        }
+        ToolArgs::RopeBridge { input, knots } => {
+            let input = read_tool_input(&input)?;
+            println!("{}", year_2022::day_09::rope_bridge(&input, knots)?);
+        }
+    }
+
+    Ok(())
+}
+
+fn read_tool_input(path: &Path) -> MyResult<String> {
+    let input = std::fs::read_to_string(path)
+        .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+    Ok(common::parsing::normalize_input(&input))
+}
+
+fn execute_solver(func: SolverFunc, input: &str) -> anyhow::Result<String> {
+    let input = common::parsing::normalize_input(input);
+    func(&input)
+}
+
 fn collect_solver_map() -> MyResult<HashMap<SolverDate, SolverData<'static>>> {
     let mut map = HashMap::new();
     for solver in SOLVERS {
@@ -109,3 +194,27 @@ struct SolverMetadata<'a> {
     func: SolverFunc,
     input: &'a str,
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{execute_solver, SOLVERS};
+
+    // Run with `cargo test --release --lib -- --ignored solvers_are_line_ending_agnostic`
+    #[test]
+    #[ignore = "runs every registered solver on its full input twice"]
+    fn solvers_are_line_ending_agnostic() {
+        for solver in SOLVERS {
+            let lf_input = solver.input.replace("\r\n", "\n");
+            let crlf_input = lf_input.replace('\n', "\r\n");
+
+            let lf_result = execute_solver(solver.func, &lf_input).map_err(|e| e.to_string());
+            let crlf_result = execute_solver(solver.func, &crlf_input).map_err(|e| e.to_string());
+
+            assert_eq!(
+                lf_result, crlf_result,
+                "Different results for LF and CRLF input in year {} day {:02} part {}",
+                solver.year, solver.day, solver.part
+            );
+        }
+    }
+}
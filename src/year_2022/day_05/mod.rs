@@ -1,138 +1,264 @@
-use itertools::Itertools;
+use anyhow::{anyhow, bail, Result};
+use macros::aoc_solver;
+use once_cell::sync::Lazy;
 use regex::Regex;
 
-use crate::{print_challenge_header, MyResult};
+use crate::common::parsing::{split_blocks, split_repeated_columns};
 
 const INPUT: &str = include_str!("input.txt");
 
-pub fn solve() -> MyResult<()> {
-    print_challenge_header(5);
+static MOVE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap());
 
-    println!(
-        "1) The crates at the top of each stacks are {}",
-        get_solution_for_first_part(INPUT)?
-    );
-    println!(
-        "2) The crates at the top of each stacks are {}",
-        get_solution_for_second_part(INPUT)?
-    );
-
-    Ok(())
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Stack {
+    // Bottom crate first
+    crates: Vec<char>,
 }
 
-fn get_solution_for_first_part(input: &str) -> MyResult<String> {
-    let (mut stacks, moves) = parse_input_file(input)?;
-
-    apply_moves_to_stacks_individually(&mut stacks, &moves);
+impl Stack {
+    fn top(&self) -> Option<char> {
+        self.crates.last().copied()
+    }
+}
 
-    Ok(get_crate_labels_from_stacks(&stacks))
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Move {
+    count: usize,
+    from: usize,
+    to: usize,
+    line: usize,
 }
 
-fn get_solution_for_second_part(input_file: &str) -> MyResult<String> {
-    let (mut stacks, moves) = parse_input_file(input_file)?;
+trait CrateMover {
+    // Takes `count` crates off the stack, returned in the order they are put down
+    fn lift(stack: &mut Stack, count: usize) -> Vec<char>;
+}
 
-    apply_moves_to_stacks_in_bulck(&mut stacks, &moves);
+struct CrateMover9000;
 
-    Ok(get_crate_labels_from_stacks(&stacks))
+impl CrateMover for CrateMover9000 {
+    fn lift(stack: &mut Stack, count: usize) -> Vec<char> {
+        let mut crates = stack.crates.split_off(stack.crates.len() - count);
+        crates.reverse();
+        crates
+    }
 }
 
-fn get_crate_labels_from_stacks(stacks: &[Vec<char>]) -> String {
-    stacks
-        .iter()
-        .map(|s| s.last().unwrap_or(&' '))
-        .collect::<String>()
+struct CrateMover9001;
+
+impl CrateMover for CrateMover9001 {
+    fn lift(stack: &mut Stack, count: usize) -> Vec<char> {
+        stack.crates.split_off(stack.crates.len() - count)
+    }
 }
 
-fn parse_input_file(input: &str) -> MyResult<(Vec<Vec<char>>, Vec<(u32, usize, usize)>)> {
-    let mut reversed_stacks = Vec::new();
-    let mut moves = Vec::new();
-    for line in input.lines() {
-        if !line.starts_with("move") {
-            let indexed_crates = parse_stacks(&line)?;
-            for (index, crate_label) in indexed_crates {
-                while reversed_stacks.len() < index + 1 {
-                    reversed_stacks.push(Vec::new());
-                }
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Ship {
+    stacks: Vec<Stack>,
+}
 
-                reversed_stacks[index].push(crate_label);
-            }
-        } else {
-            moves.push(parse_moves(&line)?);
+impl Ship {
+    fn apply<M: CrateMover>(&mut self, mv: &Move) -> Result<()> {
+        let available = self.stacks[mv.from].crates.len();
+        if available < mv.count {
+            bail!(
+                "Line {}: cannot move {} crates from stack {} holding {}",
+                mv.line,
+                mv.count,
+                mv.from + 1,
+                available
+            );
         }
+
+        let crates = M::lift(&mut self.stacks[mv.from], mv.count);
+        self.stacks[mv.to].crates.extend(crates);
+
+        Ok(())
     }
 
-    let stacks = reversed_stacks
-        .into_iter()
-        .map(|s| s.into_iter().rev().collect_vec())
-        .collect_vec();
+    fn top_crates(&self) -> String {
+        self.stacks
+            .iter()
+            .map(|s| s.top().unwrap_or(' '))
+            .collect::<String>()
+    }
 
-    Ok((stacks, moves))
+    fn render(&self) -> String {
+        let height = self
+            .stacks
+            .iter()
+            .map(|s| s.crates.len())
+            .max()
+            .unwrap_or(0);
+        let mut lines = (0..height)
+            .rev()
+            .map(|level| {
+                self.stacks
+                    .iter()
+                    .map(|s| match s.crates.get(level) {
+                        Some(label) => format!("[{}]", label),
+                        None => "   ".to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>();
+
+        let labels = (1..=self.stacks.len())
+            .map(|idx| format!(" {} ", idx))
+            .collect::<Vec<_>>()
+            .join(" ");
+        lines.push(labels);
+
+        lines.join("\n")
+    }
 }
 
-fn apply_moves_to_stacks_individually(
-    crate_stacks: &mut [Vec<char>],
-    actions: &[(u32, usize, usize)],
-) {
-    for &(count, source_stack, target_stack) in actions {
-        for _ in 0..count {
-            if let Some(popped_crate) = crate_stacks[source_stack].pop() {
-                crate_stacks[target_stack].push(popped_crate);
-            }
-        }
+#[aoc_solver(2022, 5, 1, INPUT)]
+fn get_solution_for_first_part(input: &str) -> Result<String> {
+    let (ship, moves) = parse_input_file(input)?;
+    run_crane::<CrateMover9000>(ship, &moves, |_, _| {})
+}
+
+#[aoc_solver(2022, 5, 2, INPUT)]
+fn get_solution_for_second_part(input_file: &str) -> Result<String> {
+    let (ship, moves) = parse_input_file(input_file)?;
+    run_crane::<CrateMover9001>(ship, &moves, |_, _| {})
+}
+
+fn run_crane<M: CrateMover>(
+    mut ship: Ship,
+    moves: &[Move],
+    mut on_step: impl FnMut(&Move, &Ship),
+) -> Result<String> {
+    for mv in moves.iter() {
+        ship.apply::<M>(mv)?;
+        on_step(mv, &ship);
     }
+
+    Ok(ship.top_crates())
 }
 
-fn apply_moves_to_stacks_in_bulck(crate_stacks: &mut [Vec<char>], actions: &[(u32, usize, usize)]) {
-    for &(count, source_stack, target_stack) in actions {
-        let start_index = crate_stacks[source_stack].len() - count as usize;
-        let crates_to_move = crate_stacks[source_stack].split_off(start_index);
-        crate_stacks[target_stack].extend(crates_to_move);
+pub(crate) fn supply_stacks(input: &str, model: u32) -> Result<String> {
+    match model {
+        9000 => render_steps::<CrateMover9000>(input),
+        9001 => render_steps::<CrateMover9001>(input),
+        _ => bail!("Unknown CrateMover model {}, expected 9000 or 9001", model),
     }
 }
 
-fn parse_stacks(line: &str) -> MyResult<Vec<(usize, char)>> {
-    let mut result = Vec::new();
-    for (index, chunk) in line.chars().chunks(4).into_iter().enumerate() {
-        let l = chunk.collect::<String>();
+fn render_steps<M: CrateMover>(input: &str) -> Result<String> {
+    let (ship, moves) = parse_input_file(input)?;
+    let mut frames = vec![ship.render()];
+    let top_crates = run_crane::<M>(ship, &moves, |mv, ship| {
+        frames.push(format!(
+            "move {} from {} to {}\n{}",
+            mv.count,
+            mv.from + 1,
+            mv.to + 1,
+            ship.render()
+        ))
+    })?;
+    frames.push(format!("Top crates: {}", top_crates));
+
+    Ok(frames.join("\n\n"))
+}
 
-        if l.len() < 3 {
-            return Err("Malformed stacks".into());
-        }
+fn parse_input_file(input: &str) -> Result<(Ship, Vec<Move>)> {
+    let blocks = split_blocks(input);
+    let [drawing, moves] = blocks.as_slice() else {
+        bail!("Expected the stack drawing and the moves separated by an empty line");
+    };
+
+    let ship = parse_stacks(drawing)?;
+    let first_move_line = drawing.lines().count() + 2;
+    let moves = moves
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| parse_move(line, first_move_line + idx, ship.stacks.len()))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok((ship, moves))
+}
 
-        if l.chars().any(|c| c.is_ascii_digit()) {
-            break;
+fn parse_stacks(drawing: &str) -> Result<Ship> {
+    let lines = drawing.lines().collect::<Vec<_>>();
+    let Some((label_line, crate_lines)) = lines.split_last() else {
+        bail!("Stack drawing is empty");
+    };
+
+    let stack_count = label_line.split_whitespace().count();
+    for (idx, label) in label_line.split_whitespace().enumerate() {
+        if label.parse::<usize>().ok() != Some(idx + 1) {
+            bail!(
+                "Line {}: expected stack label {}, found {}",
+                lines.len(),
+                idx + 1,
+                label
+            );
         }
+    }
 
-        if l.chars().all(|c| c == ' ') {
-            continue;
-        }
+    let mut stacks = vec![Stack::default(); stack_count];
+    for (line_idx, line) in crate_lines.iter().enumerate().rev() {
+        for (column, cell) in split_repeated_columns(line, 3, 1)?.into_iter().enumerate() {
+            if cell.trim().is_empty() {
+                continue;
+            }
 
-        let crate_label = l.chars().nth(1).unwrap();
-        if !crate_label.is_ascii_uppercase() {
-            return Err("Invalid crate label".into());
+            let label = match cell.as_bytes() {
+                [b'[', label, b']'] if label.is_ascii_uppercase() => *label as char,
+                _ => bail!("Line {}: invalid crate {:?}", line_idx + 1, cell),
+            };
+
+            let Some(stack) = stacks.get_mut(column) else {
+                bail!(
+                    "Line {}: crate {} is outside of the {} stacks",
+                    line_idx + 1,
+                    label,
+                    stack_count
+                );
+            };
+
+            stack.crates.push(label);
         }
-
-        result.push((index, crate_label));
     }
 
-    Ok(result)
+    Ok(Ship { stacks })
 }
 
-fn parse_moves(line: &str) -> MyResult<(u32, usize, usize)> {
-    // for line in lines {}
-    let reg = Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap();
-    let cap = reg.captures(line).unwrap();
-    let (count, source_stack, target_stack) = (
-        cap[1].parse()?,
-        cap[2].parse::<i32>()? - 1,
-        cap[3].parse::<i32>()? - 1,
-    );
+fn parse_move(line: &str, line_number: usize, stack_count: usize) -> Result<Move> {
+    let cap = MOVE_REGEX
+        .captures(line)
+        .ok_or_else(|| anyhow!("Line {}: invalid move {:?}", line_number, line))?;
+
+    let count = cap[1].parse::<usize>()?;
+    let stack_index = |value: &str| -> Result<usize> {
+        match value.parse::<usize>()? {
+            idx @ 1.. if idx <= stack_count => Ok(idx - 1),
+            idx => bail!(
+                "Line {}: stack {} does not exist, expected 1 to {}",
+                line_number,
+                idx,
+                stack_count
+            ),
+        }
+    };
 
-    if count < 1 || source_stack < 0 || target_stack < 0 {
-        return Err("Invalid index for move".into());
+    if count == 0 {
+        bail!(
+            "Line {}: a move needs to move at least one crate",
+            line_number
+        );
     }
 
-    Ok((count, source_stack as usize, target_stack as usize))
+    Ok(Move {
+        count,
+        from: stack_index(&cap[2])?,
+        to: stack_index(&cap[3])?,
+        line: line_number,
+    })
 }
 
 #[cfg(test)]
@@ -161,4 +287,64 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "MCD");
     }
+
+    #[test]
+    fn get_solution_for_second_part_real() {
+        let result = get_solution_for_second_part(INPUT);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), "CQQBBJFCS");
+    }
+
+    #[test]
+    fn render_example_steps() {
+        let frames = supply_stacks(EXAMPLE_INPUT, 9000).unwrap();
+        let frames = frames.split("\n\n").collect::<Vec<_>>();
+
+        assert_eq!(frames.len(), 6);
+        assert_eq!(frames[5], "Top crates: CMZ");
+        assert_eq!(
+            frames[0],
+            "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 "
+        );
+        assert_eq!(
+            frames[1],
+            "move 1 from 2 to 1\n[D]        \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 "
+        );
+    }
+
+    #[test]
+    fn reject_unknown_crane_model() {
+        let err = supply_stacks(EXAMPLE_INPUT, 9002).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unknown CrateMover model 9002, expected 9000 or 9001"
+        );
+    }
+
+    #[test]
+    fn reject_moves_from_unknown_stack() {
+        let input = EXAMPLE_INPUT.replace("move 3 from 1 to 3", "move 3 from 1 to 4");
+        let err = get_solution_for_first_part(&input).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Line 7: stack 4 does not exist, expected 1 to 3"
+        );
+    }
+
+    #[test]
+    fn reject_moves_of_missing_crates() {
+        let input = EXAMPLE_INPUT.replace("move 3 from 1 to 3", "move 4 from 1 to 3");
+        let err = get_solution_for_first_part(&input).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Line 7: cannot move 4 crates from stack 1 holding 3"
+        );
+    }
+
+    #[test]
+    fn reject_invalid_crates() {
+        let input = EXAMPLE_INPUT.replace("[N]", "(N)");
+        let err = get_solution_for_first_part(&input).unwrap_err();
+        assert_eq!(err.to_string(), "Line 2: invalid crate \"(N)\"");
+    }
 }
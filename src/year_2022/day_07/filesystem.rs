@@ -0,0 +1,306 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, bail, Context, Result};
+
+pub type EntryId = usize;
+
+const ROOT: EntryId = 0;
+
+#[derive(Debug)]
+enum EntryKind {
+    Dir {
+        children: BTreeMap<String, EntryId>,
+        listed: bool,
+    },
+    File {
+        size: u64,
+    },
+}
+
+#[derive(Debug)]
+struct Entry {
+    name: String,
+    parent: Option<EntryId>,
+    kind: EntryKind,
+}
+
+#[derive(Debug)]
+pub struct FileSystem {
+    entries: Vec<Entry>,
+    sizes: Vec<u64>,
+}
+
+impl FileSystem {
+    pub fn from_transcript(transcript: &str) -> Result<Self> {
+        let mut fs = FileSystem {
+            entries: vec![Entry {
+                name: "/".to_string(),
+                parent: None,
+                kind: EntryKind::Dir {
+                    children: BTreeMap::new(),
+                    listed: false,
+                },
+            }],
+            sizes: Vec::new(),
+        };
+
+        let mut cwd = ROOT;
+        let mut listing = false;
+        for (idx, line) in transcript.lines().enumerate() {
+            fs.apply_line(line, &mut cwd, &mut listing)
+                .with_context(|| format!("Invalid transcript in line {}", idx + 1))?;
+        }
+
+        fs.compute_sizes();
+
+        Ok(fs)
+    }
+
+    pub fn root(&self) -> EntryId {
+        ROOT
+    }
+
+    pub fn lookup(&self, path: &str) -> Option<EntryId> {
+        let relative = path.strip_prefix('/')?;
+        relative
+            .split('/')
+            .filter(|name| !name.is_empty())
+            .try_fold(ROOT, |current, name| match &self.entries[current].kind {
+                EntryKind::Dir { children, .. } => children.get(name).copied(),
+                EntryKind::File { .. } => None,
+            })
+    }
+
+    pub fn path(&self, id: EntryId) -> String {
+        let mut names = Vec::new();
+        let mut current = id;
+        while let Some(parent) = self.entries[current].parent {
+            names.push(self.entries[current].name.as_str());
+            current = parent;
+        }
+        names.reverse();
+
+        format!("/{}", names.join("/"))
+    }
+
+    pub fn size(&self, id: EntryId) -> u64 {
+        self.sizes[id]
+    }
+
+    pub fn is_dir(&self, id: EntryId) -> bool {
+        matches!(self.entries[id].kind, EntryKind::Dir { .. })
+    }
+
+    pub fn directories(&self) -> impl Iterator<Item = EntryId> + '_ {
+        (0..self.entries.len()).filter(|&id| self.is_dir(id))
+    }
+
+    pub fn render(&self, id: EntryId) -> String {
+        let mut lines = Vec::new();
+        self.render_entry(id, 0, &mut lines);
+        lines.join("\n")
+    }
+
+    fn render_entry(&self, id: EntryId, depth: usize, lines: &mut Vec<String>) {
+        let entry = &self.entries[id];
+        let kind = if self.is_dir(id) { "dir" } else { "file" };
+        lines.push(format!(
+            "{}- {} ({}, size={})",
+            "  ".repeat(depth),
+            entry.name,
+            kind,
+            self.sizes[id]
+        ));
+
+        if let EntryKind::Dir { children, .. } = &entry.kind {
+            for &child in children.values() {
+                self.render_entry(child, depth + 1, lines);
+            }
+        }
+    }
+
+    fn apply_line(&mut self, line: &str, cwd: &mut EntryId, listing: &mut bool) -> Result<()> {
+        if let Some(target) = line.strip_prefix("$ cd ") {
+            *cwd = self.change_dir(*cwd, target)?;
+            *listing = false;
+        } else if line == "$ ls" {
+            self.start_listing(*cwd)?;
+            *listing = true;
+        } else if line.starts_with('$') {
+            bail!("Unknown command {}", line);
+        } else if *listing {
+            self.add_listed_entry(*cwd, line)?;
+        } else {
+            bail!("Directory entry {} outside of a listing", line);
+        }
+
+        Ok(())
+    }
+
+    fn change_dir(&self, cwd: EntryId, target: &str) -> Result<EntryId> {
+        match target {
+            "/" => Ok(ROOT),
+            ".." => self.entries[cwd]
+                .parent
+                .ok_or_else(|| anyhow!("Cannot leave the root directory")),
+            name => match &self.entries[cwd].kind {
+                EntryKind::Dir { children, .. } => children
+                    .get(name)
+                    .copied()
+                    .filter(|&child| self.is_dir(child))
+                    .ok_or_else(|| {
+                        anyhow!("cd into unknown directory {}", self.child_path(cwd, name))
+                    }),
+                EntryKind::File { .. } => unreachable!("the working directory is always a dir"),
+            },
+        }
+    }
+
+    fn start_listing(&mut self, cwd: EntryId) -> Result<()> {
+        let path = self.path(cwd);
+        let EntryKind::Dir { listed, .. } = &mut self.entries[cwd].kind else {
+            unreachable!("the working directory is always a dir");
+        };
+
+        if *listed {
+            bail!("Directory {} is listed twice", path);
+        }
+        *listed = true;
+
+        Ok(())
+    }
+
+    fn add_listed_entry(&mut self, cwd: EntryId, line: &str) -> Result<()> {
+        let (info, name) = line
+            .split_once(' ')
+            .ok_or_else(|| anyhow!("Invalid directory entry {}", line))?;
+
+        let kind = match info {
+            "dir" => EntryKind::Dir {
+                children: BTreeMap::new(),
+                listed: false,
+            },
+            size => EntryKind::File {
+                size: size
+                    .parse()
+                    .with_context(|| format!("Invalid file size {}", size))?,
+            },
+        };
+
+        let id = self.entries.len();
+        let path = self.child_path(cwd, name);
+        let EntryKind::Dir { children, .. } = &mut self.entries[cwd].kind else {
+            unreachable!("the working directory is always a dir");
+        };
+        if children.insert(name.to_string(), id).is_some() {
+            bail!("Entry {} is listed twice", path);
+        }
+
+        self.entries.push(Entry {
+            name: name.to_string(),
+            parent: Some(cwd),
+            kind,
+        });
+
+        Ok(())
+    }
+
+    fn child_path(&self, parent: EntryId, name: &str) -> String {
+        let path = self.path(parent);
+        format!("{}/{}", path.trim_end_matches('/'), name)
+    }
+
+    // Entries are always created after their parent, so walking backwards sees every child
+    // before its parent
+    fn compute_sizes(&mut self) {
+        self.sizes = self
+            .entries
+            .iter()
+            .map(|entry| match entry.kind {
+                EntryKind::File { size } => size,
+                EntryKind::Dir { .. } => 0,
+            })
+            .collect();
+
+        for id in (1..self.entries.len()).rev() {
+            if let Some(parent) = self.entries[id].parent {
+                self.sizes[parent] += self.sizes[id];
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FileSystem;
+
+    const EXAMPLE_INPUT: &str = include_str!("example.txt");
+
+    #[test]
+    fn lookup_paths() {
+        let fs = FileSystem::from_transcript(EXAMPLE_INPUT).unwrap();
+
+        let e = fs.lookup("/a/e").unwrap();
+        assert_eq!(fs.size(e), 584);
+        assert_eq!(fs.path(e), "/a/e");
+        assert_eq!(fs.size(fs.lookup("/").unwrap()), 48381165);
+        assert!(!fs.is_dir(fs.lookup("/d/k").unwrap()));
+        assert_eq!(fs.lookup("/a/x"), None);
+        assert_eq!(fs.lookup("/b.txt/x"), None);
+    }
+
+    #[test]
+    fn render_tree() {
+        let fs = FileSystem::from_transcript(EXAMPLE_INPUT).unwrap();
+
+        let expected = "\
+- / (dir, size=48381165)
+  - a (dir, size=94853)
+    - e (dir, size=584)
+      - i (file, size=584)
+    - f (file, size=29116)
+    - g (file, size=2557)
+    - h.lst (file, size=62596)
+  - b.txt (file, size=14848514)
+  - c.dat (file, size=8504156)
+  - d (dir, size=24933642)
+    - d.ext (file, size=5626152)
+    - d.log (file, size=8033020)
+    - j (file, size=4060174)
+    - k (file, size=7214296)";
+        assert_eq!(fs.render(fs.root()), expected);
+    }
+
+    #[test]
+    fn reject_cd_into_unknown_dir() {
+        let err = FileSystem::from_transcript("$ cd /\n$ ls\ndir a\n$ cd b").unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "Invalid transcript in line 4: cd into unknown directory /b"
+        );
+    }
+
+    #[test]
+    fn reject_duplicate_listings() {
+        let err = FileSystem::from_transcript("$ cd /\n$ ls\n1 a\n$ ls\n1 a").unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "Invalid transcript in line 4: Directory / is listed twice"
+        );
+
+        let err = FileSystem::from_transcript("$ ls\n1 a\ndir a").unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "Invalid transcript in line 3: Entry /a is listed twice"
+        );
+    }
+
+    #[test]
+    fn reject_entries_outside_of_listing() {
+        let err = FileSystem::from_transcript("$ cd /\n1 a").unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "Invalid transcript in line 2: Directory entry 1 a outside of a listing"
+        );
+    }
+}
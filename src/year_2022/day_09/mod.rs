@@ -1,115 +1,94 @@
-use std::collections::HashSet;
+mod rope;
 
-use crate::{print_challenge_header, MyResult};
+use anyhow::{anyhow, bail, Context, Result};
+use macros::aoc_solver;
 
-const INPUT: &str = include_str!("input.txt");
-
-#[derive(Debug)]
-enum Direction {
-    Up,
-    Down,
-    Right,
-    Left,
-}
+use crate::common::math_2d::Vec2;
 
-pub fn solve() -> MyResult<()> {
-    print_challenge_header(9);
+use self::rope::Rope;
 
-    println!(
-        "1) The number of position visited at least once is {}",
-        solve_first_part(INPUT)?
-    );
-    println!(
-        "2) The number of position visited at least once is {}",
-        solve_second_part(INPUT)?
-    );
+const INPUT: &str = include_str!("input.txt");
 
-    Ok(())
+#[derive(Debug, Clone, Copy)]
+struct Move {
+    direction: Vec2<i64>,
+    count: u32,
 }
 
-fn solve_first_part(input: &str) -> MyResult<u32> {
-    let moves = parse_moves(input)?;
-
-    let mut head_pos: (i32, i32) = (0, 0);
-    let mut tail_pos: (i32, i32) = (0, 0);
-    let mut visited_positions = HashSet::from([tail_pos]);
-
-    for (direction, count) in moves {
-        for _ in 0..count {
-            head_pos = get_new_head_position(&direction, head_pos);
-
-            tail_pos = get_tail_position(head_pos, tail_pos);
-
-            visited_positions.insert(tail_pos);
-        }
-    }
-
-    Ok(visited_positions.len() as u32)
+#[aoc_solver(2022, 9, 1, INPUT)]
+fn solve_first_part(input: &str) -> Result<String> {
+    let rope = simulate(input, 2)?;
+    Ok(rope.tail_visited().len().to_string())
 }
 
-fn get_tail_position(head_pos: (i32, i32), tail_pos: (i32, i32)) -> (i32, i32) {
-    let difference = (head_pos.0 - tail_pos.0, head_pos.1 - tail_pos.1);
-    if (difference.0).abs() > 1 || (difference.1).abs() > 1 {
-        return (
-            tail_pos.0 + difference.0.clamp(-1, 1),
-            tail_pos.1 + difference.1.clamp(-1, 1),
-        );
-    }
-
-    tail_pos
+#[aoc_solver(2022, 9, 2, INPUT)]
+fn solve_second_part(input: &str) -> Result<String> {
+    let rope = simulate(input, 10)?;
+    Ok(rope.tail_visited().len().to_string())
 }
 
-fn get_new_head_position(direction: &Direction, head_pos: (i32, i32)) -> (i32, i32) {
-    match *direction {
-        Direction::Up => (head_pos.0, head_pos.1 + 1),
-        Direction::Down => (head_pos.0, head_pos.1 - 1),
-        Direction::Left => (head_pos.0 - 1, head_pos.1),
-        Direction::Right => (head_pos.0 + 1, head_pos.1),
+pub(crate) fn rope_bridge(input: &str, knot_count: usize) -> Result<String> {
+    let rope = simulate(input, knot_count)?;
+    let mut lines = vec![
+        rope.render().trim_end_matches('\n').to_string(),
+        format!("Head at {:?}, tail at {:?}", rope.head(), rope.tail()),
+    ];
+    for knot in 0..rope.knot_count() {
+        lines.push(format!(
+            "Knot {} visited {} positions",
+            knot,
+            rope.visited(knot).len()
+        ));
     }
-}
 
-fn parse_moves(input: &str) -> MyResult<Vec<(Direction, u32)>> {
-    let mut moves = Vec::new();
-    for line in input.lines() {
-        let Some((direction, count)) = line.split_once(' ') else {
-            return Err("Invalid line".into());
-        };
-
-        let count = count.parse::<u32>()?;
-        let direction = match direction {
-            "U" => Direction::Up,
-            "D" => Direction::Down,
-            "R" => Direction::Right,
-            "L" => Direction::Left,
-            _ => return Err("Invalid direction".into()),
-        };
-
-        moves.push((direction, count));
-    }
-    Ok(moves)
+    Ok(lines.join("\n"))
 }
 
-fn solve_second_part(input: &str) -> MyResult<u32> {
+fn simulate(input: &str, knot_count: usize) -> Result<Rope> {
     let moves = parse_moves(input)?;
 
-    let mut tail_positions = vec![(0i32, 0i32); 10];
-    let mut visited_positions = HashSet::from([*tail_positions.last().unwrap()]);
-
-    for (direction, count) in moves {
-        for _ in 0..count {
-            // set head
-            tail_positions[0] = get_new_head_position(&direction, tail_positions[0]);
-
-            // adjust tails
-            for i in 1..tail_positions.len() {
-                tail_positions[i] = get_tail_position(tail_positions[i - 1], tail_positions[i]);
-            }
-
-            visited_positions.insert(*tail_positions.last().unwrap());
+    let mut rope = Rope::new(knot_count)?;
+    for mv in moves {
+        for _ in 0..mv.count {
+            rope.step(mv.direction);
         }
     }
 
-    Ok(visited_positions.len() as u32)
+    Ok(rope)
+}
+
+fn parse_moves(input: &str) -> Result<Vec<Move>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            parse_move(line).with_context(|| format!("Invalid move in line {}", idx + 1))
+        })
+        .collect()
+}
+
+fn parse_move(line: &str) -> Result<Move> {
+    let (direction, count) = line
+        .split_once(' ')
+        .ok_or_else(|| anyhow!("Expected a direction and a count, got {:?}", line))?;
+
+    // The y axis points down, so moving up decreases y
+    let direction = match direction {
+        "U" => Vec2::new(0, -1),
+        "D" => Vec2::new(0, 1),
+        "L" => Vec2::new(-1, 0),
+        "R" => Vec2::new(1, 0),
+        "UL" => Vec2::new(-1, -1),
+        "UR" => Vec2::new(1, -1),
+        "DL" => Vec2::new(-1, 1),
+        "DR" => Vec2::new(1, 1),
+        _ => bail!("Unknown direction {}", direction),
+    };
+
+    Ok(Move {
+        direction,
+        count: count.parse()?,
+    })
 }
 
 #[cfg(test)]
@@ -123,34 +102,79 @@ mod tests {
     fn solve_first_part_example() {
         let result = solve_first_part(EXAMPLE_INPUT);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), 13);
+        assert_eq!(result.unwrap(), "13");
     }
 
     #[test]
     fn solve_first_part_real() {
         let result = solve_first_part(INPUT);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), 6018);
+        assert_eq!(result.unwrap(), "6018");
     }
 
     #[test]
     fn solve_second_part_example() {
         let result = solve_second_part(EXAMPLE_INPUT);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), 1);
+        assert_eq!(result.unwrap(), "1");
     }
 
     #[test]
     fn solve_second_part_example_2() {
         let result = solve_second_part(EXAMPLE_INPUT_2);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), 36);
+        assert_eq!(result.unwrap(), "36");
     }
 
     #[test]
     fn solve_second_part_real() {
         let result = solve_second_part(INPUT);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), 2619);
+        assert_eq!(result.unwrap(), "2619");
+    }
+
+    #[test]
+    fn knots_follow_the_same_path_regardless_of_rope_length() {
+        let short = simulate(INPUT, 2).unwrap();
+        let long = simulate(INPUT, 10).unwrap();
+
+        assert_eq!(short.visited(1), long.visited(1));
+        assert_eq!(short.head(), long.head());
+    }
+
+    #[test]
+    fn render_example() {
+        let rope = simulate(EXAMPLE_INPUT, 2).unwrap();
+
+        assert_eq!(rope.tail(), rope.head() + Vec2::new(-1, 0));
+        assert_eq!(rope.render(), "..##.\n...##\n.1H##\n....#\ns###.\n");
+    }
+
+    #[test]
+    fn diagonal_moves() {
+        let rope = simulate("UR 3\nL 1", 3).unwrap();
+
+        assert_eq!(rope.render(), "..H\n..1\n.2.\ns..\n");
+        assert_eq!(rope.visited(0).len(), 5);
+        assert_eq!(rope.tail_visited().len(), 2);
+    }
+
+    #[test]
+    fn rope_bridge_summary() {
+        let result = rope_bridge(EXAMPLE_INPUT, 2).unwrap();
+        let lines = result.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines[5], "Head at (2, -2), tail at (1, -2)");
+        assert_eq!(lines[7], "Knot 1 visited 13 positions");
+    }
+
+    #[test]
+    fn reject_invalid_moves() {
+        let err = parse_moves("U 1\nX 2").unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "Invalid move in line 2: Unknown direction X"
+        );
+        assert!(Rope::new(0).is_err());
     }
 }
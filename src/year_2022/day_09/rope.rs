@@ -0,0 +1,81 @@
+use std::collections::HashSet;
+
+use anyhow::{bail, Result};
+
+use crate::common::{
+    debugging::render_sparse_grid,
+    math_2d::{Point, SparseGrid, Vec2},
+};
+
+pub struct Rope {
+    knots: Vec<Point<i64>>,
+    visited: Vec<HashSet<Point<i64>>>,
+}
+
+impl Rope {
+    pub fn new(knot_count: usize) -> Result<Self> {
+        if knot_count == 0 {
+            bail!("A rope needs at least one knot");
+        }
+
+        let start = Point::new(0, 0);
+        Ok(Self {
+            knots: vec![start; knot_count],
+            visited: vec![HashSet::from([start]); knot_count],
+        })
+    }
+
+    pub fn knot_count(&self) -> usize {
+        self.knots.len()
+    }
+
+    pub fn head(&self) -> Point<i64> {
+        self.knots[0]
+    }
+
+    pub fn tail(&self) -> Point<i64> {
+        self.knots[self.knots.len() - 1]
+    }
+
+    pub fn visited(&self, knot: usize) -> &HashSet<Point<i64>> {
+        &self.visited[knot]
+    }
+
+    pub fn tail_visited(&self) -> &HashSet<Point<i64>> {
+        &self.visited[self.knots.len() - 1]
+    }
+
+    // Moves the head by one step, which may be diagonal, and lets the other knots follow
+    pub fn step(&mut self, direction: Vec2<i64>) {
+        self.knots[0] += direction;
+        self.visited[0].insert(self.knots[0]);
+
+        for idx in 1..self.knots.len() {
+            let diff = self.knots[idx - 1] - self.knots[idx];
+            if diff.x.abs() <= 1 && diff.y.abs() <= 1 {
+                // Knots further down the rope can only move if this one did
+                break;
+            }
+
+            self.knots[idx] += Vec2::new(diff.x.signum(), diff.y.signum());
+            self.visited[idx].insert(self.knots[idx]);
+        }
+    }
+
+    // Draws the positions visited by the tail, the start and all knots, where knots closer to
+    // the head are drawn on top
+    pub fn render(&self) -> String {
+        let mut grid = SparseGrid::new('.');
+        grid.extend(self.tail_visited().iter().map(|point| (*point, '#')));
+        grid.insert(Point::new(0, 0), 's');
+        for (idx, knot) in self.knots.iter().enumerate().rev() {
+            let label = match idx {
+                0 => 'H',
+                idx => char::from_digit(idx as u32, 36).unwrap_or('*'),
+            };
+            grid.insert(*knot, label);
+        }
+
+        render_sparse_grid(&grid, |c| *c, &HashSet::new())
+    }
+}
@@ -1,4 +1,13 @@
-use crate::{print_challenge_header, MyResult};
+use std::fmt::Display;
+
+use anyhow::{anyhow, bail, Context, Result};
+use macros::aoc_solver;
+
+use crate::common::{
+    debugging::log_verbose,
+    ocr,
+    vm::{Machine, RegisterFile, Vm},
+};
 
 const INPUT: &str = include_str!("input.txt");
 
@@ -8,24 +17,17 @@ enum Instruction {
     Noop,
 }
 
-pub fn solve() -> MyResult<()> {
-    print_challenge_header(10);
-
-    println!(
-        "1) The aggregated signal strenth is {}",
-        solve_part_one(&INPUT)?
-    );
-    println!(
-        "2) The CTR produces the following image: \n{}",
-        solve_part_two(&INPUT)?
-    );
-
-    Ok(())
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction::Add(value) => write!(f, "addx {}", value),
+            Instruction::Noop => write!(f, "noop"),
+        }
+    }
 }
 
 struct Cpu {
-    cycle: u32,
-    register_x: i32,
+    registers: RegisterFile<i32, 1>,
 }
 
 struct Program {
@@ -33,104 +35,117 @@ struct Program {
 }
 
 impl Program {
-    fn from_file(input: &str) -> MyResult<Self> {
-        let mut instructions = Vec::new();
-        for line in input.lines() {
-            if line == "noop" {
-                instructions.push(Instruction::Noop);
-                continue;
-            }
+    fn from_file(input: &str) -> Result<Self> {
+        let instructions = input
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| {
+                Self::parse_instruction(line)
+                    .with_context(|| format!("Invalid instruction in line {}", idx + 1))
+            })
+            .collect::<Result<_>>()?;
 
-            if line.starts_with("addx ") {
-                let Some((_, value)) = line.split_once(' ') else {
-                    return Err("invalid instruction".into());
-                };
+        Ok(Program { instructions })
+    }
 
-                instructions.push(Instruction::Add(value.parse()?))
-            }
+    fn parse_instruction(line: &str) -> Result<Instruction> {
+        if line == "noop" {
+            return Ok(Instruction::Noop);
         }
-        Ok(Program { instructions })
+
+        let Some(value) = line.strip_prefix("addx ") else {
+            bail!("Unknown instruction {:?}", line);
+        };
+
+        Ok(Instruction::Add(value.parse()?))
     }
 }
 
 impl Cpu {
     fn new() -> Self {
         Cpu {
-            cycle: 0,
-            register_x: 1,
+            registers: RegisterFile::new(["X"], [1]),
         }
     }
 
-    fn execute(mut self, program: &Program, mut callback: impl FnMut(&Cpu)) {
-        for instruction in program.instructions.iter() {
-            self.handle_instruction_cycles(&instruction, &mut callback);
+    fn register_x(&self) -> i32 {
+        self.registers.get(0)
+    }
+
+    fn execute(self, program: &Program, mut callback: impl FnMut(u32, &Cpu)) -> Result<()> {
+        let mut vm = Vm::new(self, program.instructions.clone());
+        vm.run_with_hook(|cycle, cpu| callback(cycle as u32, cpu))?;
+
+        Ok(())
+    }
+}
+
+impl Machine for Cpu {
+    type Word = Instruction;
+    type Instruction = Instruction;
+    type Registers = RegisterFile<i32, 1>;
+
+    fn decode(program: &[Instruction], pc: usize) -> Result<(Instruction, usize)> {
+        Ok((program[pc], 1))
+    }
+
+    fn cycles(instruction: &Instruction) -> u64 {
+        match instruction {
+            Instruction::Noop => 1,
+            Instruction::Add(_) => 2,
         }
     }
 
-    fn apply_instruction(&mut self, instruction: &Instruction) {
+    fn execute(&mut self, instruction: Instruction) -> Result<Option<usize>> {
         match instruction {
             Instruction::Noop => {
                 // do nothing
             }
             Instruction::Add(value) => {
-                self.register_x += value;
+                *self.registers.get_mut(0) += value;
             }
         }
-    }
 
-    fn handle_instruction_cycles(
-        &mut self,
-        instruction: &Instruction,
-        callback: &mut impl FnMut(&Cpu),
-    ) {
-        let mut cycles_to_wait = self.get_cycle_count_for_instruction(&instruction);
-        loop {
-            if cycles_to_wait > 0 {
-                cycles_to_wait -= 1;
-            }
-
-            self.cycle += 1;
-
-            callback(&self);
-
-            if cycles_to_wait == 0 {
-                self.apply_instruction(&instruction);
-                break;
-            }
-        }
+        Ok(None)
     }
 
-    fn get_cycle_count_for_instruction(&self, instruction: &Instruction) -> u32 {
-        match instruction {
-            Instruction::Noop => 1,
-            Instruction::Add(_) => 2,
-        }
+    fn registers(&self) -> &RegisterFile<i32, 1> {
+        &self.registers
     }
 }
 
-fn solve_part_one(input: &str) -> MyResult<i32> {
+#[aoc_solver(2022, 10, 1, INPUT)]
+fn solve_part_one(input: &str) -> Result<String> {
     let program = Program::from_file(input)?;
     let cpu = Cpu::new();
 
     let mut summed_signal_strength = 0;
 
-    cpu.execute(&program, |x| {
-        if x.cycle == 20 || (x.cycle > 20 && (x.cycle - 20) % 40 == 0) {
-            summed_signal_strength += x.cycle as i32 * x.register_x;
+    cpu.execute(&program, |cycle, x| {
+        if cycle == 20 || (cycle > 20 && (cycle - 20) % 40 == 0) {
+            summed_signal_strength += cycle as i32 * x.register_x();
         }
-    });
+    })?;
+
+    Ok(summed_signal_strength.to_string())
+}
+
+#[aoc_solver(2022, 10, 2, INPUT)]
+fn solve_part_two(input: &str) -> Result<String> {
+    let image = draw_screen(input)?;
+    log_verbose(&image);
 
-    Ok(summed_signal_strength)
+    read_screen(&image)
 }
 
-fn solve_part_two(input: &str) -> MyResult<String> {
+fn draw_screen(input: &str) -> Result<String> {
     let program = Program::from_file(input)?;
     let cpu = Cpu::new();
 
     let mut output = String::new();
     let mut drawing_pos = 0;
-    cpu.execute(&program, |cpu| {
-        let pixel_positions = cpu.register_x - 1..cpu.register_x + 2;
+    cpu.execute(&program, |_, cpu| {
+        let pixel_positions = cpu.register_x() - 1..cpu.register_x() + 2;
 
         if pixel_positions.contains(&drawing_pos) {
             output.push('#');
@@ -143,11 +158,15 @@ fn solve_part_two(input: &str) -> MyResult<String> {
         if drawing_pos == 0 {
             output.push('\n');
         }
-    });
+    })?;
 
     Ok(output.trim().to_string())
 }
 
+fn read_screen(image: &str) -> Result<String> {
+    ocr::recognize_str(image).map_err(|e| anyhow!("Could not read the screen: {}", e))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -158,19 +177,19 @@ mod tests {
     fn solve_part_one_example() {
         let result = solve_part_one(EXAMPLE_INPUT);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), 13140);
+        assert_eq!(result.unwrap(), "13140");
     }
 
     #[test]
     fn solve_part_one_real() {
         let result = solve_part_one(INPUT);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), 13720);
+        assert_eq!(result.unwrap(), "13720");
     }
 
     #[test]
-    fn solve_part_two_example() {
-        let result = solve_part_two(EXAMPLE_INPUT);
+    fn draw_screen_example() {
+        let result = draw_screen(EXAMPLE_INPUT);
 
         let output = "##..##..##..##..##..##..##..##..##..##..
 ###...###...###...###...###...###...###.
@@ -183,8 +202,8 @@ mod tests {
     }
 
     #[test]
-    fn solve_part_two_real() {
-        let result = solve_part_two(INPUT);
+    fn draw_screen_real() {
+        let result = draw_screen(INPUT);
 
         let output = "####.###..#..#.###..#..#.####..##..#..#.
 #....#..#.#..#.#..#.#..#....#.#..#.#..#.
@@ -193,6 +212,30 @@ mod tests {
 #....#..#.#..#.#.#..#..#.#....#..#.#..#.
 #....###...##..#..#.#..#.####..##..#..#.";
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), output);
+        let image = result.unwrap();
+        assert_eq!(image, output);
+        assert_eq!(read_screen(&image).unwrap(), "FBURHZCH");
+    }
+
+    #[test]
+    fn solve_part_two_real() {
+        assert_eq!(solve_part_two(INPUT).unwrap(), "FBURHZCH");
+    }
+
+    #[test]
+    fn solve_part_two_example_is_not_text() {
+        let err = solve_part_two(EXAMPLE_INPUT).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Could not read the screen: Unrecognized glyphs"));
+    }
+
+    #[test]
+    fn reject_unknown_instructions() {
+        let err = solve_part_one("noop\nmulx 3").unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "Invalid instruction in line 2: Unknown instruction \"mulx 3\""
+        );
     }
 }
@@ -1,17 +1,19 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, fmt::Display};
 
 use itertools::Itertools;
-use num::{BigUint, Zero};
 use regex::Regex;
 
-use crate::{print_challenge_header, MyResult};
+use anyhow::{anyhow, bail, Result};
+use macros::aoc_solver;
+
+use crate::common::{debugging::log_verbose, number_theory::checked_lcm_of};
 
 const INPUT: &str = include_str!("input.txt");
 
 #[derive(Debug)]
 enum OperationValue {
     Old,
-    Number(BigUint),
+    Number(u64),
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -23,7 +25,7 @@ enum OperationType {
 
 #[derive(Debug)]
 struct MonkeyTest {
-    divider: u32,
+    divider: u64,
     true_target: u32,
     false_target: u32,
 }
@@ -31,17 +33,17 @@ struct MonkeyTest {
 #[derive(Debug)]
 struct MonkeyConfig {
     id: u32,
-    items: VecDeque<BigUint>,
+    items: VecDeque<u64>,
     operation_value: OperationValue,
     operation_type: OperationType,
     test: MonkeyTest,
-    inpect_count: u32,
+    inpect_count: u64,
 }
 
 impl MonkeyConfig {
-    fn parse_config(lines: &[String]) -> MyResult<Self> {
+    fn parse_config(lines: &[String]) -> Result<Self> {
         if lines.len() != 6 {
-            return Err("Invalid line count".into());
+            bail!("Invalid line count");
         }
 
         let header_regex = Regex::new(r"^Monkey (\d+):$")?;
@@ -52,50 +54,53 @@ impl MonkeyConfig {
         let test_false_regex = Regex::new(r"^If false: throw to monkey (\d+)$")?;
 
         let Some(header_match) = header_regex.captures(lines[0].trim()) else {
-            return Err("Invalid header".into());
+            bail!("Invalid header");
         };
 
         let id = header_match[1].parse::<u32>()?;
 
         let Some(items_capture) = items_regex.captures(lines[1].trim()) else {
-            return Err("Invalid starting items".into());
+            bail!("Invalid starting items");
         };
 
         let items = items_capture[1]
             .split(',')
-            .map(|i| i.trim().parse::<BigUint>())
+            .map(|i| i.trim().parse::<u64>())
             .try_collect()?;
 
         let Some(operation_capture) = operation_regex.captures(lines[2].trim()) else {
-            return Err("Invalid operation".into());
+            bail!("Invalid operation");
         };
 
         let operation_type = match &operation_capture[1] {
             "*" => OperationType::Mul,
             "+" => OperationType::Add,
             "-" => OperationType::Sub,
-            _ => return Err("Invalid operation".into()),
+            _ => bail!("Invalid operation"),
         };
 
         let operation_value = match &operation_capture[2] {
             "old" => OperationValue::Old,
-            _ => OperationValue::Number(operation_capture[2].parse::<BigUint>()?),
+            _ => OperationValue::Number(operation_capture[2].parse::<u64>()?),
         };
 
         let Some(test_capture) = test_regex.captures(lines[3].trim()) else {
-            return Err("Invalid test format".into());
+            bail!("Invalid test format");
         };
 
-        let divider = test_capture[1].parse::<u32>()?;
+        let divider = test_capture[1].parse::<u64>()?;
+        if divider == 0 {
+            bail!("Test divider must not be zero");
+        }
 
         let Some(test_true_capture) = test_true_regex.captures(lines[4].trim()) else {
-            return Err("Invalid test true format".into());
+            bail!("Invalid test true format");
         };
 
         let true_target = test_true_capture[1].parse::<u32>()?;
 
         let Some(test_false_capture) = test_false_regex.captures(lines[5].trim()) else {
-            return Err("Invalid test false format".into());
+            bail!("Invalid test false format");
         };
 
         let false_target = test_false_capture[1].parse::<u32>()?;
@@ -113,85 +118,132 @@ impl MonkeyConfig {
             },
         })
     }
-}
 
-pub fn solve() -> MyResult<()> {
-    print_challenge_header(11);
+    // Worries are only ever tested for divisibility, so without the relief division they can be
+    // kept modulo the product of all dividers
+    fn inspect(&self, item: u64, worry_div: u64, modulus: Option<u64>) -> Result<u64> {
+        let operation_val = match self.operation_value {
+            OperationValue::Old => item,
+            OperationValue::Number(value) => value,
+        };
+
+        let (item, operation_val) = (item as u128, operation_val as u128);
+        let inspected_item = match self.operation_type {
+            OperationType::Add => item + operation_val,
+            OperationType::Mul => item * operation_val,
+            OperationType::Sub => match modulus {
+                Some(modulus) => item + modulus as u128 - operation_val % modulus as u128,
+                None => item
+                    .checked_sub(operation_val)
+                    .ok_or_else(|| anyhow!("Worry level dropped below zero"))?,
+            },
+        };
 
-    println!(
-        "1) The level of monkey business is {}",
-        solve_part_one(INPUT)?
-    );
-    println!(
-        "2) the level of monkey business is {}",
-        solve_part_two(INPUT)?
-    );
+        let reduced_item = match modulus {
+            Some(modulus) => inspected_item % modulus as u128,
+            None => inspected_item / worry_div as u128,
+        };
 
-    Ok(())
-}
+        u64::try_from(reduced_item).map_err(|_| anyhow!("Worry level exceeds u64"))
+    }
 
-fn solve_part_one(input: &str) -> MyResult<u32> {
-    execute_monkey_game(input, 20, 3)
+    fn target(&self, item: u64) -> u32 {
+        if item.is_multiple_of(self.test.divider) {
+            self.test.true_target
+        } else {
+            self.test.false_target
+        }
+    }
 }
 
-fn solve_part_two(file: &str) -> MyResult<u32> {
-    execute_monkey_game(file, 10_000, 1)
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RoundStats {
+    round: usize,
+    inspect_counts: Vec<u64>,
+    items: Vec<Vec<u64>>,
 }
 
-fn execute_monkey_game(file: &str, rounds: usize, worry_div: u32) -> MyResult<u32> {
-    let mut monkeys = parse_input(file)?;
+impl Display for RoundStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "== After round {} ==", self.round)?;
+        for (idx, (count, items)) in self
+            .inspect_counts
+            .iter()
+            .zip(self.items.iter())
+            .enumerate()
+        {
+            writeln!(
+                f,
+                "Monkey {} inspected items {} times, holding: {}",
+                idx,
+                count,
+                items.iter().join(", ")
+            )?;
+        }
 
-    for _ in 0..rounds {
-        for idx in 0..monkeys.len() {
-            // println!("Monkey: {}:", monkeys[idx].id);
-            loop {
-                let Some(item) = &monkeys[idx].items.pop_front() else {
-                    break;
-                };
+        Ok(())
+    }
+}
 
-                monkeys[idx].inpect_count += 1;
+#[aoc_solver(2022, 11, 1, INPUT)]
+fn solve_part_one(input: &str) -> Result<String> {
+    Ok(execute_monkey_game(input, 20, 3)?.to_string())
+}
 
-                let operation_val = match &monkeys[idx].operation_value {
-                    OperationValue::Old => item,
-                    OperationValue::Number(value) => value,
-                };
+#[aoc_solver(2022, 11, 2, INPUT)]
+fn solve_part_two(input: &str) -> Result<String> {
+    Ok(execute_monkey_game(input, 10_000, 1)?.to_string())
+}
 
-                let inspected_item = match &monkeys[idx].operation_type {
-                    OperationType::Add => item + operation_val,
-                    OperationType::Mul => item * operation_val,
-                    OperationType::Sub => item - operation_val,
-                };
+fn execute_monkey_game(file: &str, rounds: usize, worry_div: u64) -> Result<u64> {
+    execute_monkey_game_with_stats(file, rounds, worry_div, |stats| {
+        if stats.round == rounds {
+            log_verbose(stats);
+        }
+    })
+}
 
-                let item_before_test = inspected_item / worry_div;
+fn execute_monkey_game_with_stats(
+    file: &str,
+    rounds: usize,
+    worry_div: u64,
+    mut on_round: impl FnMut(&RoundStats),
+) -> Result<u64> {
+    if worry_div == 0 {
+        bail!("Worry divider must not be zero");
+    }
 
-                let target_monkey_id =
-                    if &item_before_test % monkeys[idx].test.divider == Zero::zero() {
-                        monkeys[idx].test.true_target
-                    } else {
-                        monkeys[idx].test.false_target
-                    };
+    let mut monkeys = parse_input(file)?;
+    let modulus = if worry_div == 1 {
+        let modulus = checked_lcm_of(monkeys.iter().map(|m| m.test.divider))
+            .ok_or_else(|| anyhow!("Least common multiple of the test dividers exceeds u64"))?;
+        Some(modulus)
+    } else {
+        None
+    };
+
+    for round in 1..=rounds {
+        for idx in 0..monkeys.len() {
+            while let Some(item) = monkeys[idx].items.pop_front() {
+                monkeys[idx].inpect_count += 1;
+
+                let item = monkeys[idx].inspect(item, worry_div, modulus)?;
+                let target_monkey_id = monkeys[idx].target(item);
 
                 let target_monkey = monkeys.iter_mut().find(|m| m.id == target_monkey_id);
-                target_monkey.unwrap().items.push_back(item_before_test);
-
-                // println!("  Inspecting {}", item);
-                // println!(
-                //     "    Operation {:?} by {} to {}",
-                //     monkeys[idx].operation_type, operation_val, inspected_item
-                // );
-                // println!("    Dividied by 3 to {} ", item_before_test);
-                // println!(
-                //     "    Item {} thrown to {} ",
-                //     item_before_test, target_monkey_id
-                // );
+                target_monkey.unwrap().items.push_back(item);
             }
         }
-    }
 
-    // println!("Monkey 1: {:?}", monkeys[0].items);
-    // println!("Monkey 2: {:?}", monkeys[1].items);
-    // println!("Monkey 3: {:?}", monkeys[2].items);
-    // println!("Monkey 4: {:?}", monkeys[3].items);
+        on_round(&RoundStats {
+            round,
+            inspect_counts: monkeys.iter().map(|m| m.inpect_count).collect(),
+            items: monkeys
+                .iter()
+                .map(|m| m.items.iter().copied().collect())
+                .collect(),
+        });
+    }
 
     let inspection_counts = monkeys
         .iter()
@@ -199,12 +251,13 @@ fn execute_monkey_game(file: &str, rounds: usize, worry_div: u32) -> MyResult<u3
         .sorted()
         .rev()
         .take(2)
-        .fold(1_u32, |acc, val| acc * val);
+        .try_fold(1_u64, |acc, val| acc.checked_mul(val))
+        .ok_or_else(|| anyhow!("Monkey business exceeds u64"))?;
 
     Ok(inspection_counts)
 }
 
-fn parse_input(input: &str) -> MyResult<Vec<MonkeyConfig>> {
+fn parse_input(input: &str) -> Result<Vec<MonkeyConfig>> {
     let mut configs = Vec::new();
 
     let mut line_buffer = Vec::new();
@@ -221,7 +274,13 @@ fn parse_input(input: &str) -> MyResult<Vec<MonkeyConfig>> {
         configs.push(MonkeyConfig::parse_config(&line_buffer)?);
     }
 
-    // TODO: add checks to ensure that referenced ids actually exist
+    for monkey in configs.iter() {
+        for target in [monkey.test.true_target, monkey.test.false_target] {
+            if !configs.iter().any(|m| m.id == target) {
+                bail!("Monkey {} throws to unknown monkey {}", monkey.id, target);
+            }
+        }
+    }
 
     Ok(configs)
 }
@@ -236,21 +295,52 @@ mod tests {
     fn solve_part_one_example() {
         let result = solve_part_one(EXAMPLE_INPUT);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), 10605);
+        assert_eq!(result.unwrap(), "10605");
     }
 
     #[test]
     fn solve_part_one_real() {
         let result = solve_part_one(INPUT);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), 182293);
+        assert_eq!(result.unwrap(), "182293");
     }
 
-    // text executions takes too long
-    //    #[test]
-    //    fn solve_part_two_example() {
-    //        let result = solve_part_two(EXAMPLE_INPUT);
-    //        assert!(result.is_ok());
-    //        assert_eq!(result.unwrap(), 2713310158);
-    //    }
+    #[test]
+    fn solve_part_two_example() {
+        let result = solve_part_two(EXAMPLE_INPUT);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), "2713310158");
+    }
+
+    #[test]
+    fn solve_part_two_real() {
+        let result = solve_part_two(INPUT);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), "54832778815");
+    }
+
+    #[test]
+    fn round_stats() {
+        let mut stats = Vec::new();
+        execute_monkey_game_with_stats(EXAMPLE_INPUT, 20, 1, |s| stats.push(s.clone())).unwrap();
+
+        assert_eq!(stats[0].inspect_counts, [2, 4, 3, 6]);
+        assert_eq!(stats[19].inspect_counts, [99, 97, 8, 103]);
+    }
+
+    #[test]
+    fn round_stats_with_relief() {
+        let mut stats = Vec::new();
+        execute_monkey_game_with_stats(EXAMPLE_INPUT, 1, 3, |s| stats.push(s.clone())).unwrap();
+
+        assert_eq!(
+            stats[0].to_string(),
+            "== After round 1 ==
+Monkey 0 inspected items 2 times, holding: 20, 23, 27, 26
+Monkey 1 inspected items 4 times, holding: 2080, 25, 167, 207, 401, 1046
+Monkey 2 inspected items 3 times, holding: 
+Monkey 3 inspected items 5 times, holding: 
+"
+        );
+    }
 }
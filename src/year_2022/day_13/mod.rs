@@ -1,184 +1,180 @@
 use std::cmp::Ordering;
-use std::collections::VecDeque;
 
-use itertools::Itertools;
+use anyhow::{anyhow, bail, Result};
+use macros::aoc_solver;
 
-use crate::{print_challenge_header, MyResult};
+use crate::common::parsing::split_blocks;
 
-use CompareResult::*;
 use PacketData::*;
 
 const INPUT: &str = include_str!("input.txt");
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, Clone)]
 enum PacketData {
     List(Vec<PacketData>),
-    Value(u8),
+    Value(u32),
 }
 
-#[derive(Debug, PartialEq)]
-enum CompareResult {
-    Valid,
-    Invalid,
-    Equivalent,
+impl Ord for PacketData {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Value(left), Value(right)) => left.cmp(right),
+            // Lists compare element-wise and the shorter list comes first when all elements match
+            (List(left), List(right)) => left.cmp(right),
+            (Value(left), List(_)) => List(vec![Value(*left)]).cmp(other),
+            (List(_), Value(right)) => self.cmp(&List(vec![Value(*right)])),
+        }
+    }
 }
 
-pub fn solve() -> MyResult<()> {
-    print_challenge_header(13);
-
-    println!("The sum of indices is {}", solve_part_one(INPUT)?);
-
-    Ok(())
+impl PartialOrd for PacketData {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
-fn solve_part_one(input: &str) -> MyResult<u32> {
-    let mut result = 0;
-    let mut chunk_index = 1;
-    for chunk in &input.lines().chunks(3) {
-        let chunk: Vec<&str> = chunk.collect_vec();
+// Equality follows the puzzle ordering, so `3` and `[3]` are equal packets. Compare the
+// `Debug` output to check the structure instead.
+impl PartialEq for PacketData {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
 
-        if chunk.len() < 2 {
-            return Err("Invalid file format".into());
-        }
+impl Eq for PacketData {}
 
-        let left = &chunk[0];
-        let right = &chunk[1];
+#[aoc_solver(2022, 13, 1, INPUT)]
+fn solve_part_one(input: &str) -> Result<String> {
+    let mut result = 0;
+    for (idx, pair) in split_blocks(input).iter().enumerate() {
+        let packets = pair.lines().map(parse_line).collect::<Result<Vec<_>>>()?;
+        let [left, right] = packets.as_slice() else {
+            bail!("Pair {} must contain exactly two packets", idx + 1);
+        };
 
-        // println!("comparing {:?} with {:?}", left, right);
-        if compare_packets(&parse_line(&left)?, &parse_line(&right)?) == Valid {
-            println!("Chunk {} is valid", chunk_index);
-            result += chunk_index;
+        if left < right {
+            result += idx + 1;
         }
-
-        chunk_index += 1;
     }
 
-    Ok(result)
+    Ok(result.to_string())
 }
 
-fn solve_part_two() -> MyResult<u32> {
-    todo!()
+#[aoc_solver(2022, 13, 2, INPUT)]
+fn solve_part_two(input: &str) -> Result<String> {
+    let packets = input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(parse_line)
+        .collect::<Result<Vec<_>>>()?;
+
+    // Sorting is not needed, a divider's index is the number of packets before it
+    let first_divider = parse_line("[[2]]")?;
+    let second_divider = parse_line("[[6]]")?;
+    let first_idx = 1 + packets.iter().filter(|p| **p < first_divider).count();
+    let second_idx = 2 + packets.iter().filter(|p| **p < second_divider).count();
+
+    Ok((first_idx * second_idx).to_string())
 }
 
-fn parse_line(line: &str) -> MyResult<PacketData> {
+fn parse_line(line: &str) -> Result<PacketData> {
     if line.is_empty() {
-        return Err("Line is empty".into());
+        bail!("Line is empty");
     }
 
-    if !line.ends_with("]") {
-        return Err("Line does not end with ]".into());
-    }
+    let mut parser = PacketParser {
+        chars: line.chars().collect(),
+        pos: 0,
+    };
 
-    if !line.starts_with("[") {
-        return Err("Line does not start with [".into());
+    if parser.peek() != Some('[') {
+        bail!(
+            "Expected '[' at position 0, found {:?}",
+            line.chars().next().unwrap()
+        );
     }
 
-    let mut token_stack = VecDeque::new();
-    let chars = line.chars().collect_vec();
-    let mut idx = 0;
+    let packet = parser.parse_packet()?;
+    if let Some(c) = parser.peek() {
+        bail!(
+            "Unexpected {:?} at position {} after the end of the packet",
+            c,
+            parser.pos
+        );
+    }
 
-    loop {
-        if idx >= chars.len() {
-            break;
-        }
+    Ok(packet)
+}
 
-        let token = chars[idx];
+struct PacketParser {
+    chars: Vec<char>,
+    pos: usize,
+}
 
-        if token.is_ascii_digit() {
-            let mut digit = token.to_string();
+impl PacketParser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
 
-            loop {
-                if !chars[idx + 1].is_ascii_digit() {
-                    break;
-                }
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.pos += 1;
+        c
+    }
 
-                digit.push(chars[idx + 1]);
-                idx += 1;
-            }
-            token_stack.push_back(digit);
-        } else {
-            token_stack.push_back(token.to_string());
+    fn parse_packet(&mut self) -> Result<PacketData> {
+        match self.peek() {
+            Some('[') => self.parse_list(),
+            Some(c) if c.is_ascii_digit() => self.parse_value(),
+            Some(c) => bail!(
+                "Expected '[' or a digit at position {}, found {:?}",
+                self.pos,
+                c
+            ),
+            None => bail!(
+                "Unexpected end of line at position {}, expected '[' or a digit",
+                self.pos
+            ),
         }
-
-        idx += 1;
     }
 
-    let packet = resolve_tokens(&mut token_stack)?;
-
-    Ok(packet)
-}
-
-fn resolve_tokens(tokens: &mut VecDeque<String>) -> MyResult<PacketData> {
-    let mut result_stack: Vec<PacketData> = Vec::new();
+    fn parse_list(&mut self) -> Result<PacketData> {
+        self.next();
 
-    loop {
-        let Some(token) = tokens.pop_front() else {
-            return Ok(result_stack.first().unwrap().clone());
-        };
+        let mut items = Vec::new();
+        if self.peek() == Some(']') {
+            self.next();
+            return Ok(List(items));
+        }
 
-        match token.as_str() {
-            "[" => {
-                let packet = resolve_tokens(tokens)?;
-                result_stack.push(packet);
-            }
-            "]" => {
-                let p = PacketData::List(result_stack);
-                return Ok(p);
-            }
-            "," => {
-                continue;
+        loop {
+            items.push(self.parse_packet()?);
+
+            let pos = self.pos;
+            match self.next() {
+                Some(',') => continue,
+                Some(']') => return Ok(List(items)),
+                Some(c) => bail!("Expected ',' or ']' at position {}, found {:?}", pos, c),
+                None => bail!(
+                    "Unexpected end of line at position {}, expected ',' or ']'",
+                    pos
+                ),
             }
-            _ => result_stack.push(PacketData::Value(token.to_string().parse()?)),
         }
     }
-}
 
-fn compare_packets(left: &PacketData, right: &PacketData) -> CompareResult {
-    match (left, right) {
-        (Value(l), Value(r)) => match l.cmp(r) {
-            Ordering::Less => Valid,
-            Ordering::Equal => Equivalent,
-            Ordering::Greater => Invalid,
-        },
-        (List(left), List(right)) => {
-            if right.len() < left.len() {
-                return Invalid;
-            }
+    fn parse_value(&mut self) -> Result<PacketData> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
 
-            for (left, right) in left.iter().zip(right.iter()) {
-                match compare_packets(left, right) {
-                    Valid => return Valid,
-                    Invalid => return Invalid,
-                    Equivalent => continue,
-                }
-            }
+        let digits = self.chars[start..self.pos].iter().collect::<String>();
+        let value = digits
+            .parse()
+            .map_err(|e| anyhow!("Invalid value {} at position {}: {}", digits, start, e))?;
 
-            Valid
-        }
-        (List(left), Value(_)) => {
-            if let Some(first_left_val) = left.first() {
-                let r = compare_packets(first_left_val, right);
-                if r == Equivalent {
-                    Valid
-                } else {
-                    r
-                }
-            } else {
-                Valid
-            }
-        }
-        (Value(_), List(right)) => {
-            if let Some(first_right_val) = right.first() {
-                let r = compare_packets(left, first_right_val);
-                if r == Equivalent {
-                    Valid
-                } else {
-                    r
-                }
-            } else {
-                Valid
-            }
-        }
+        Ok(Value(value))
     }
 }
 
@@ -189,18 +185,15 @@ mod tests {
     const EXAMPLE_INPUT: &str = include_str!("example.txt");
 
     mod compare_packets {
-        use crate::year_2022::day_13::compare_packets;
+        use std::cmp::Ordering::*;
+
         use crate::year_2022::day_13::parse_line;
-        use crate::year_2022::day_13::CompareResult::*;
+        use crate::year_2022::day_13::PacketData;
         use crate::year_2022::day_13::PacketData::*;
 
-        // #[test]
-        // fn compare_lists_righ_less_values_failes() {
-        //     assert!(compare_packets(
-        //         &parse_line("[1]").unwrap(),
-        //         &parse_line("[[2,3,4]]").unwrap()
-        //     ));
-        // }
+        fn compare_packets(left: &PacketData, right: &PacketData) -> std::cmp::Ordering {
+            left.cmp(right)
+        }
 
         #[test]
         fn compare_list_with_value_left_success() {
@@ -209,7 +202,7 @@ mod tests {
                     &parse_line("[1]").unwrap(),
                     &parse_line("[[2,3,4]]").unwrap(),
                 ),
-                Valid
+                Less
             );
         }
 
@@ -220,7 +213,7 @@ mod tests {
                     &parse_line("[[2,3,4]]").unwrap(),
                     &parse_line("[4]").unwrap()
                 ),
-                Valid
+                Less
             );
         }
 
@@ -231,7 +224,7 @@ mod tests {
                     &parse_line("[1,1,3,1,1]").unwrap(),
                     &parse_line("[1,1,5,1,1]").unwrap()
                 ),
-                Valid
+                Less
             );
         }
 
@@ -242,7 +235,7 @@ mod tests {
                     &parse_line("[1,1,3,1,7]").unwrap(),
                     &parse_line("[1,1,5,1,1]").unwrap()
                 ),
-                Valid
+                Less
             );
         }
 
@@ -253,7 +246,7 @@ mod tests {
                     &parse_line("[7,7,7,7]").unwrap(),
                     &parse_line("[7,7,7]").unwrap()
                 ),
-                Invalid
+                Greater
             );
         }
 
@@ -264,7 +257,7 @@ mod tests {
                     &parse_line("[9]").unwrap(),
                     &parse_line("[[8,7,6]]").unwrap()
                 ),
-                Invalid
+                Greater
             );
         }
 
@@ -272,7 +265,7 @@ mod tests {
         fn compare_empty_nested_lists_right_runs_out() {
             assert_eq!(
                 compare_packets(&parse_line("[[[]]]").unwrap(), &parse_line("[[]]").unwrap()),
-                Invalid
+                Greater
             );
         }
 
@@ -283,7 +276,7 @@ mod tests {
                     &parse_line("[5,6,7]").unwrap(),
                     &parse_line("[5,6,0]").unwrap()
                 ),
-                Invalid
+                Greater
             );
         }
 
@@ -291,38 +284,52 @@ mod tests {
         fn compare_empty_list_with_filled() {
             assert_eq!(
                 compare_packets(&List(Vec::new()), &List(vec![Value(3)])),
-                Valid
+                Less
             );
         }
 
         #[test]
         fn equal_values_are_equivalent() {
-            assert_eq!(compare_packets(&Value(1), &Value(1)), Equivalent);
+            assert_eq!(compare_packets(&Value(1), &Value(1)), Equal);
         }
 
         #[test]
         fn lower_left_value_returns_true() {
-            assert_eq!(compare_packets(&Value(1), &Value(2)), Valid);
+            assert_eq!(compare_packets(&Value(1), &Value(2)), Less);
+        }
+
+        #[test]
+        fn value_equals_single_value_list() {
+            assert_eq!(Value(3), parse_line("[[[3]]]").unwrap());
         }
 
         #[test]
         fn higher_left_value_returns_false() {
-            assert_eq!(compare_packets(&Value(2), &Value(1)), Invalid);
+            assert_eq!(compare_packets(&Value(2), &Value(1)), Greater);
         }
     }
 
     mod parse_line {
-        use crate::year_2022::day_13::PacketData::*;
+        use crate::year_2022::day_13::PacketData::{self, *};
 
         use super::parse_line;
 
+        fn parse_error(line: &str) -> String {
+            parse_line(line).unwrap_err().to_string()
+        }
+
+        // `PartialEq` treats `9` and `[9]` as equal, so compare the parsed structure instead
+        fn structure(packet: &PacketData) -> String {
+            format!("{:?}", packet)
+        }
+
         #[test]
         fn packet_with_multi_character_number() {
             let result = parse_line("[10]");
             let expected = List(vec![Value(10)]);
 
             assert!(result.is_ok());
-            assert_eq!(result.unwrap(), expected);
+            assert_eq!(structure(&result.unwrap()), structure(&expected));
         }
         #[test]
         fn packet_with_mixed_values_and_lists() {
@@ -335,7 +342,7 @@ mod tests {
             ]);
 
             assert!(result.is_ok());
-            assert_eq!(result.unwrap(), expected);
+            assert_eq!(structure(&result.unwrap()), structure(&expected));
         }
 
         #[test]
@@ -347,7 +354,7 @@ mod tests {
             ]);
 
             assert!(result.is_ok());
-            assert_eq!(result.unwrap(), expected);
+            assert_eq!(structure(&result.unwrap()), structure(&expected));
         }
 
         #[test]
@@ -356,7 +363,7 @@ mod tests {
             let expected = List(vec![List(Vec::new()), List(Vec::new())]);
 
             assert!(result.is_ok());
-            assert_eq!(result.unwrap(), expected);
+            assert_eq!(structure(&result.unwrap()), structure(&expected));
         }
 
         #[test]
@@ -365,7 +372,7 @@ mod tests {
             let expected = List(vec![List(Vec::new())]);
 
             assert!(result.is_ok());
-            assert_eq!(result.unwrap(), expected);
+            assert_eq!(structure(&result.unwrap()), structure(&expected));
         }
 
         #[test]
@@ -374,7 +381,7 @@ mod tests {
             let expected = List(vec![Value(9), Value(1), Value(3)]);
 
             assert!(result.is_ok());
-            assert_eq!(result.unwrap(), expected);
+            assert_eq!(structure(&result.unwrap()), structure(&expected));
         }
 
         #[test]
@@ -383,7 +390,15 @@ mod tests {
             let expected = List(vec![Value(9)]);
 
             assert!(result.is_ok());
-            assert_eq!(result.unwrap(), expected);
+            assert_eq!(structure(&result.unwrap()), structure(&expected));
+        }
+
+        #[test]
+        fn values_and_single_value_lists_differ_in_structure() {
+            let result = parse_line("[[9]]").unwrap();
+
+            assert_eq!(result, List(vec![Value(9)]));
+            assert_ne!(structure(&result), structure(&List(vec![Value(9)])));
         }
 
         #[test]
@@ -401,7 +416,7 @@ mod tests {
             let result = parse_line("[]");
 
             assert!(result.is_ok());
-            assert_eq!(result.unwrap(), expected);
+            assert_eq!(structure(&result.unwrap()), structure(&expected));
         }
 
         #[test]
@@ -409,7 +424,10 @@ mod tests {
             let result = parse_line("[0,9,1");
 
             assert!(result.is_err());
-            assert_eq!(result.unwrap_err().to_string(), "Line does not end with ]");
+            assert_eq!(
+                result.unwrap_err().to_string(),
+                "Unexpected end of line at position 6, expected ',' or ']'"
+            );
         }
 
         #[test]
@@ -419,7 +437,34 @@ mod tests {
             assert!(result.is_err());
             assert_eq!(
                 result.unwrap_err().to_string(),
-                "Line does not start with ["
+                "Expected '[' at position 0, found '0'"
+            );
+        }
+
+        #[test]
+        fn errors_report_position() {
+            assert_eq!(
+                parse_error("[1,[2;3]]"),
+                "Expected ',' or ']' at position 5, found ';'"
+            );
+            assert_eq!(
+                parse_error("[1,,2]"),
+                "Expected '[' or a digit at position 3, found ','"
+            );
+            assert_eq!(
+                parse_error("[1]]"),
+                "Unexpected ']' at position 3 after the end of the packet"
+            );
+        }
+
+        #[test]
+        fn packet_with_large_value() {
+            assert_eq!(
+                structure(&parse_line("[1000]").unwrap()),
+                structure(&List(vec![Value(1000)]))
+            );
+            assert!(
+                parse_error("[99999999999]").starts_with("Invalid value 99999999999 at position 1")
             );
         }
     }
@@ -428,6 +473,24 @@ mod tests {
     fn solve_part_one_example() {
         let result = solve_part_one(EXAMPLE_INPUT);
         assert!(result.is_ok(), "{:?}", result);
-        assert_eq!(result.unwrap(), 13);
+        assert_eq!(result.unwrap(), "13");
+    }
+
+    #[test]
+    fn solve_part_one_real() {
+        let result = solve_part_one(INPUT).unwrap();
+        assert_eq!(result, "5393");
+    }
+
+    #[test]
+    fn solve_part_two_example() {
+        let result = solve_part_two(EXAMPLE_INPUT).unwrap();
+        assert_eq!(result, "140");
+    }
+
+    #[test]
+    fn solve_part_two_real() {
+        let result = solve_part_two(INPUT).unwrap();
+        assert_eq!(result, "26712");
     }
 }
@@ -1,10 +1,15 @@
 use std::ops::Index;
 
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
 use itertools::Itertools;
 use macros::aoc_solver;
 use rayon::prelude::*;
 
+use crate::common::{
+    intervals::{IntervalSet, PiecewiseMapping},
+    parsing::{parse_key_values, split_blocks},
+};
+
 const INPUT: &str = include_str!("input.txt");
 
 #[derive(Debug)]
@@ -21,7 +26,7 @@ struct PuzzleInput {
 
 #[aoc_solver(2023, 5, 1, INPUT)]
 fn solve_part_one(input: &str) -> Result<String> {
-    let puzzle_input = parse_input(input);
+    let puzzle_input = parse_input(input)?;
     let res = determine_lowest_location(&puzzle_input.seeds, &puzzle_input.mappings);
 
     Ok(res.to_string())
@@ -29,41 +34,42 @@ fn solve_part_one(input: &str) -> Result<String> {
 
 #[aoc_solver(2023, 5, 2, INPUT)]
 pub fn solve_part_two(input: &str) -> Result<String> {
-    let puzzle = parse_input(input);
+    let puzzle = parse_input(input)?;
 
-    assert!(puzzle.seeds.len() % 2 == 0);
+    if puzzle.seeds.len() % 2 != 0 {
+        bail!("Expected the seeds to be pairs of start and length");
+    }
 
-    let seeds = puzzle
+    let seed_ranges = puzzle
         .seeds
-        .iter()
-        .copied()
-        .enumerate()
-        .filter(|(index, _)| index % 2 == 0)
-        .map(|(_, value)| value)
-        .collect_vec();
+        .chunks(2)
+        .map(|pair| pair[0]..pair[0] + pair[1])
+        .collect::<IntervalSet<_>>();
 
-    let lengths = puzzle
-        .seeds
-        .into_iter()
-        .enumerate()
-        .filter(|(index, _)| index % 2 == 1)
-        .map(|(_, value)| value)
-        .collect_vec();
+    let lowest_location = determine_lowest_location_of_ranges(seed_ranges, &puzzle.mappings)
+        .ok_or_else(|| anyhow!("No seeds to map"))?;
 
-    let mut lowest_location = u64::MAX;
-    for (start_seed, length) in seeds.into_iter().zip(lengths) {
-        let seed_range = (start_seed..(start_seed + length))
-            .into_iter()
-            .collect_vec();
+    Ok(lowest_location.to_string())
+}
 
-        let location = determine_lowest_location(&seed_range, &puzzle.mappings);
+fn determine_lowest_location_of_ranges(
+    seeds: IntervalSet<u64>,
+    mappings: &[Vec<MappingRange>],
+) -> Option<u64> {
+    let locations = mappings.iter().fold(seeds, |ranges, mapping| {
+        create_piecewise_mapping(mapping).map_set(&ranges)
+    });
 
-        if location < lowest_location {
-            lowest_location = location;
-        }
+    locations.min_value()
+}
+
+fn create_piecewise_mapping(mapping: &[MappingRange]) -> PiecewiseMapping<u64> {
+    let mut piecewise_mapping = PiecewiseMapping::new();
+    for range in mapping {
+        piecewise_mapping.add(range.start..range.start + range.length, range.destination);
     }
 
-    Ok(lowest_location.to_string())
+    piecewise_mapping
 }
 
 fn determine_lowest_location(seeds: &[u64], mappings: &[Vec<MappingRange>]) -> u64 {
@@ -125,33 +131,28 @@ fn binary_search(
     return binary_search(list, target, start_index, middle_index - 1);
 }
 
-fn parse_input(input: &str) -> PuzzleInput {
-    PuzzleInput {
-        seeds: parse_seeds(input),
+fn parse_input(input: &str) -> Result<PuzzleInput> {
+    let puzzle = PuzzleInput {
+        seeds: parse_seeds(input)?,
         mappings: parse_mappings(input),
-    }
+    };
+
+    Ok(puzzle)
 }
 
-fn parse_seeds(input: &str) -> Vec<u64> {
-    let seeds = input
-        .lines()
-        .into_iter()
-        .take(1)
-        .map(|line| {
-            line.split(' ')
-                .skip(1)
-                .map(|str| str.parse::<u64>().unwrap())
-        })
-        .flatten()
-        .collect::<Vec<_>>();
+fn parse_seeds(input: &str) -> Result<Vec<u64>> {
+    let first_line = input.lines().next().unwrap_or_default();
+    let (key, seeds) = parse_key_values::<u64>(first_line)?;
+    if key != "seeds" {
+        bail!("Expected the first line to list the seeds");
+    }
 
-    seeds
+    Ok(seeds)
 }
 
-// TODO: implement os independent line endings
 fn parse_mappings(input: &str) -> Vec<Vec<MappingRange>> {
-    let mappings = input
-        .split("\n\n")
+    let mappings = split_blocks(input)
+        .into_iter()
         .skip(1)
         .map(parse_mapping_paragraph)
         .collect_vec();
@@ -1,17 +1,19 @@
-use std::collections::HashSet;
-
-use anyhow::Result;
+use anyhow::{anyhow, bail, Context, Result};
 use itertools::Itertools;
 use macros::aoc_solver;
 
-use self::{joker_rule::JokerRule, no_joker_rule::NoJokerRule};
+use self::{joker_rule::JokerRule, no_joker_rule::NoJokerRule, wildcard_rule::WildcardRule};
 
 mod joker_rule;
 mod no_joker_rule;
+mod wildcard_rule;
 
 const INPUT: &str = include_str!("input.txt");
 
-#[derive(PartialEq, PartialOrd, Debug, Clone)]
+const CARD_LABELS: &str = "23456789TJQKA";
+const HAND_SIZE: usize = 5;
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
 enum HandType {
     Five = 7,
     Four = 6,
@@ -22,98 +24,148 @@ enum HandType {
     HighCard = 1,
 }
 
-#[aoc_solver(2023, 7, 1, INPUT)]
-fn solve_part_one(input: &str) -> Result<String> {
-    let mut bids = parse_input::<NoJokerRule>(input);
+trait HandRule {
+    // Value used to break ties between hands of the same type, higher is stronger
+    fn card_value(&self, card: char) -> Result<u32> {
+        card_strength(card)
+    }
 
-    bids.sort_unstable_by(|a, b| a.hand.partial_cmp(&b.hand).unwrap());
+    fn is_wildcard(&self, _card: char) -> bool {
+        false
+    }
 
-    let res: u32 = bids
-        .iter()
-        .enumerate()
-        .map(|(index, bid)| ((index + 1) as u32) * bid.bid)
-        .sum();
+    fn hand_type(&self, cards: &[char]) -> HandType {
+        let wildcards = cards.iter().filter(|&&c| self.is_wildcard(c)).count();
+        let mut counts = cards
+            .iter()
+            .filter(|&&c| !self.is_wildcard(c))
+            .counts()
+            .into_values()
+            .sorted_unstable_by(|a, b| b.cmp(a))
+            .collect_vec();
 
-    Ok(res.to_string())
-}
+        // Wildcards always form the strongest hand by copying the most common card
+        match counts.first_mut() {
+            Some(count) => *count += wildcards,
+            None => counts.push(wildcards),
+        }
 
-#[aoc_solver(2023, 7, 2, INPUT)]
-fn solve_part_two(input: &str) -> Result<String> {
-    let mut bids = parse_input::<JokerRule>(input);
+        match counts.as_slice() {
+            [5, ..] => HandType::Five,
+            [4, ..] => HandType::Four,
+            [3, 2, ..] => HandType::FullHouse,
+            [3, ..] => HandType::Triple,
+            [2, 2, ..] => HandType::TwoPair,
+            [2, ..] => HandType::OnePair,
+            _ => HandType::HighCard,
+        }
+    }
+}
 
-    bids.sort_unstable_by(|a, b| a.hand.partial_cmp(&b.hand).unwrap());
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone)]
+struct Hand {
+    r#type: HandType,
+    cards: Vec<u32>,
+}
 
-    let res: u32 = bids
-        .iter()
-        .enumerate()
-        .map(|(index, bid)| ((index + 1) as u32) * bid.bid)
-        .sum();
+impl Hand {
+    fn new(cards: &str, rule: &impl HandRule) -> Result<Self> {
+        let cards = cards.chars().collect_vec();
+        if cards.len() != HAND_SIZE {
+            bail!("A hand has {} cards, got {}", HAND_SIZE, cards.len());
+        }
 
-    Ok(res.to_string())
+        Ok(Self {
+            r#type: rule.hand_type(&cards),
+            cards: cards
+                .iter()
+                .map(|&c| rule.card_value(c))
+                .collect::<Result<_>>()?,
+        })
+    }
 }
 
-fn get_hand_type(cards: &[u32]) -> HandType {
-    let unique_values = cards.iter().collect::<HashSet<_>>().len();
-
-    let max_duplicate_count = get_max_duplicate_count(cards);
+#[derive(Debug)]
+struct Bid {
+    hand: Hand,
+    bid: u32,
+}
 
-    match unique_values {
-        5 => HandType::HighCard,
-        4 => HandType::OnePair,
-        3 if max_duplicate_count == 2 => HandType::TwoPair,
-        3 => HandType::Triple,
-        2 if max_duplicate_count == 4 => HandType::Four,
-        2 => HandType::FullHouse,
-        1 => HandType::Five,
-        _ => panic!(),
-    }
+#[aoc_solver(2023, 7, 1, INPUT)]
+fn solve_part_one(input: &str) -> Result<String> {
+    Ok(total_winnings(input, &NoJokerRule)?.to_string())
 }
 
-fn get_max_duplicate_count(cards: &[u32]) -> usize {
-    cards
-        .into_iter()
-        .unique()
-        .map(|c| count_element(c, cards))
-        .max()
-        .unwrap()
+#[aoc_solver(2023, 7, 2, INPUT)]
+fn solve_part_two(input: &str) -> Result<String> {
+    Ok(total_winnings(input, &JokerRule)?.to_string())
 }
 
-fn count_element(element: &u32, cards: &[u32]) -> usize {
-    cards.iter().filter(|&c| element == c).collect_vec().len()
+pub(crate) fn camel_cards(input: &str, wildcards: &str) -> Result<String> {
+    let rule = WildcardRule::new(wildcards)?;
+    Ok(format!("Total winnings: {}", total_winnings(input, &rule)?))
 }
 
-#[derive(Debug)]
-struct Bid<THand>
-where
-    THand: HandRule,
-{
-    hand: THand,
-    bid: u32,
+fn total_winnings(input: &str, rule: &impl HandRule) -> Result<u64> {
+    let mut bids = parse_input(input, rule)?;
+
+    bids.sort_unstable_by(|a, b| a.hand.cmp(&b.hand));
+
+    let res = bids
+        .iter()
+        .enumerate()
+        .map(|(index, bid)| ((index + 1) as u64) * u64::from(bid.bid))
+        .sum();
+
+    Ok(res)
 }
 
-impl<THand: HandRule> Bid<THand> {
-    fn from_line(line: &str) -> Self {
-        let (cards, bid) = line.split_once(' ').unwrap();
+fn card_strength(card: char) -> Result<u32> {
+    CARD_LABELS
+        .find(card)
+        .map(|idx| idx as u32 + 2)
+        .ok_or_else(|| anyhow!("Unknown card {:?}", card))
+}
 
-        Self {
-            bid: bid.parse::<u32>().unwrap(),
-            hand: THand::from_cards(cards),
-        }
-    }
+fn parse_input(input: &str, rule: &impl HandRule) -> Result<Vec<Bid>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            parse_bid(line, rule).with_context(|| format!("Invalid bid in line {}", idx + 1))
+        })
+        .collect()
 }
 
-fn parse_input<THand: HandRule>(input: &str) -> Vec<Bid<THand>> {
-    input.lines().map(|l| Bid::from_line(l)).collect_vec()
+fn parse_bid(line: &str, rule: &impl HandRule) -> Result<Bid> {
+    let (cards, bid) = line
+        .split_once(' ')
+        .ok_or_else(|| anyhow!("Expected cards and a bid, got {:?}", line))?;
+
+    Ok(Bid {
+        hand: Hand::new(cards, rule)?,
+        bid: bid.parse()?,
+    })
 }
 
 #[cfg(test)]
 mod tests {
+    use itertools::Itertools;
+
     use crate::year_2023::day_07::{solve_part_two, INPUT};
 
-    use super::solve_part_one;
+    use super::{
+        camel_cards, solve_part_one, Hand, HandRule, HandType, JokerRule, NoJokerRule,
+        WildcardRule, CARD_LABELS, HAND_SIZE,
+    };
 
     const EXAMPLE_INPUT: &str = include_str!("example.txt");
 
+    // The hand type only depends on which cards a hand holds, not on their order
+    fn all_card_multisets() -> impl Iterator<Item = Vec<char>> {
+        CARD_LABELS.chars().combinations_with_replacement(HAND_SIZE)
+    }
+
     #[test]
     fn part_one_example_input_correct() {
         let result = solve_part_one(EXAMPLE_INPUT).unwrap();
@@ -137,8 +189,88 @@ mod tests {
         let result = solve_part_two(INPUT).unwrap();
         assert_eq!(result, "250757288");
     }
-}
 
-trait HandRule {
-    fn from_cards(cards: &str) -> Self;
+    #[test]
+    fn configurable_wildcards_match_fixed_rules() {
+        assert_eq!(camel_cards(INPUT, "").unwrap(), "Total winnings: 251287184");
+        assert_eq!(
+            camel_cards(INPUT, "J").unwrap(),
+            "Total winnings: 250757288"
+        );
+        assert!(camel_cards(INPUT, "X").is_err());
+    }
+
+    #[test]
+    fn hand_types() {
+        let cases = [
+            ("AAAAA", HandType::Five),
+            ("AA8AA", HandType::Four),
+            ("23332", HandType::FullHouse),
+            ("TTT98", HandType::Triple),
+            ("23432", HandType::TwoPair),
+            ("A23A4", HandType::OnePair),
+            ("23456", HandType::HighCard),
+        ];
+
+        for (cards, expected) in cases {
+            let hand = Hand::new(cards, &NoJokerRule).unwrap();
+            assert_eq!(hand.r#type, expected, "{}", cards);
+        }
+
+        assert_eq!(
+            Hand::new("JJJJJ", &JokerRule).unwrap().r#type,
+            HandType::Five
+        );
+        assert!(Hand::new("AAAA", &NoJokerRule).is_err());
+    }
+
+    #[test]
+    fn wildcards_break_ties_as_weakest_cards() {
+        let rule = WildcardRule::new("9Q").unwrap();
+        let weak = Hand::new("Q2345", &rule).unwrap();
+        let strong = Hand::new("2Q345", &rule).unwrap();
+
+        assert_eq!(weak.r#type, strong.r#type);
+        assert!(weak < strong);
+        assert!(Hand::new("92345", &rule).unwrap() < weak);
+    }
+
+    #[test]
+    fn jokers_never_weaken_a_hand() {
+        for cards in all_card_multisets() {
+            let plain = NoJokerRule.hand_type(&cards);
+            assert!(JokerRule.hand_type(&cards) >= plain, "{:?}", cards);
+
+            for wildcard in CARD_LABELS.chars() {
+                let rule = WildcardRule::new(&wildcard.to_string()).unwrap();
+                assert!(
+                    rule.hand_type(&cards) >= plain,
+                    "{:?} with {}",
+                    cards,
+                    wildcard
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn jokers_pick_the_best_replacement() {
+        for cards in all_card_multisets() {
+            let jokers = cards.iter().positions(|&c| c == 'J').collect_vec();
+            let best = (0..jokers.len())
+                .map(|_| CARD_LABELS.chars())
+                .multi_cartesian_product()
+                .map(|replacements| {
+                    let mut replaced = cards.clone();
+                    for (&idx, card) in jokers.iter().zip(replacements) {
+                        replaced[idx] = card;
+                    }
+                    NoJokerRule.hand_type(&replaced)
+                })
+                .max()
+                .unwrap_or_else(|| NoJokerRule.hand_type(&cards));
+
+            assert_eq!(JokerRule.hand_type(&cards), best, "{:?}", cards);
+        }
+    }
 }
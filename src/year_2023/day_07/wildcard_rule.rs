@@ -0,0 +1,41 @@
+use anyhow::{bail, Result};
+use itertools::Itertools;
+
+use super::{card_strength, HandRule};
+
+// Non-wildcards are shifted by this so they rank above the value of every wildcard
+const WILDCARD_OFFSET: u32 = 13;
+
+#[derive(Debug, Clone)]
+pub struct WildcardRule {
+    wildcards: Vec<char>,
+}
+
+impl WildcardRule {
+    pub fn new(wildcards: &str) -> Result<Self> {
+        let wildcards = wildcards.chars().unique().collect_vec();
+        for &card in wildcards.iter() {
+            if card_strength(card).is_err() {
+                bail!("Wildcard {:?} is not a card label", card);
+            }
+        }
+
+        Ok(Self { wildcards })
+    }
+}
+
+impl HandRule for WildcardRule {
+    // Wildcards are weaker than all other cards, but keep their usual order among each other
+    fn card_value(&self, card: char) -> Result<u32> {
+        let strength = card_strength(card)?;
+        if self.is_wildcard(card) {
+            Ok(strength)
+        } else {
+            Ok(strength + WILDCARD_OFFSET)
+        }
+    }
+
+    fn is_wildcard(&self, card: char) -> bool {
+        self.wildcards.contains(&card)
+    }
+}
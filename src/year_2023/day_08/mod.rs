@@ -1,12 +1,16 @@
 use std::collections::HashMap;
 
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
 use itertools::Itertools;
 use macros::aoc_solver;
 use regex::Regex;
 
+use crate::common::number_theory::{checked_lcm_of, crt};
+
 const INPUT: &str = include_str!("input.txt");
 
+const MAX_HIT_COMBINATIONS: usize = 100_000;
+
 enum Direction {
     Left,
     Right,
@@ -51,32 +55,151 @@ fn solve_part_one(input: &str) -> Result<String> {
 fn solve_part_two(input: &str) -> Result<String> {
     let puzzle_input = parse_input(input);
 
-    let mut step_count = 0;
-    let mut start_nodes = puzzle_input
+    let cycles = puzzle_input
         .network
         .keys()
         .filter(|key| key.ends_with('A'))
-        .collect_vec();
+        .sorted()
+        .map(|node| analyze_cycle(node, &puzzle_input))
+        .collect::<Result<Vec<_>>>()?;
+
+    if cycles.is_empty() {
+        bail!("No start nodes ending in 'A' found");
+    }
+
+    Ok(find_first_common_hit(&cycles)?.to_string())
+}
+
+struct GhostCycle {
+    start: String,
+    offset: u64,
+    period: u64,
+    prefix_hits: Vec<u64>,
+    cycle_hits: Vec<u64>,
+}
+
+impl GhostCycle {
+    fn is_hit(&self, step: u64) -> bool {
+        if step < self.offset {
+            self.prefix_hits.contains(&step)
+        } else {
+            let position = self.offset + (step - self.offset) % self.period;
+            self.cycle_hits.contains(&position)
+        }
+    }
+}
+
+fn analyze_cycle(start: &str, puzzle_input: &PuzzleInput) -> Result<GhostCycle> {
+    let direction_count = puzzle_input.directions.len();
+    let mut first_visits: HashMap<(&str, usize), u64> = HashMap::new();
+    let mut hits = Vec::new();
 
+    let mut current_node = start;
+    let mut step_count: u64 = 0;
     loop {
-        let direction = &puzzle_input.directions[step_count % puzzle_input.directions.len()];
-        start_nodes = start_nodes
-            .iter()
-            .map(|&node| puzzle_input.network.get(node).unwrap())
-            .map(|edge| match direction {
-                Direction::Left => &edge.left,
-                Direction::Right => &edge.right,
-            })
-            .collect::<Vec<_>>();
+        let direction_idx = (step_count % direction_count as u64) as usize;
+        if let Some(&offset) = first_visits.get(&(current_node, direction_idx)) {
+            let (prefix_hits, cycle_hits) = hits.into_iter().partition(|&hit| hit < offset);
 
-        step_count += 1;
+            return Ok(GhostCycle {
+                start: start.to_string(),
+                offset,
+                period: step_count - offset,
+                prefix_hits,
+                cycle_hits,
+            });
+        }
 
-        if start_nodes.iter().all(|n| n.ends_with("Z")) {
-            break;
+        first_visits.insert((current_node, direction_idx), step_count);
+        if current_node.ends_with('Z') {
+            hits.push(step_count);
         }
+
+        let edge = puzzle_input
+            .network
+            .get(current_node)
+            .ok_or_else(|| anyhow!("Node {} is not part of the network", current_node))?;
+        current_node = match puzzle_input.directions[direction_idx] {
+            Direction::Left => &edge.left,
+            Direction::Right => &edge.right,
+        };
+
+        step_count += 1;
     }
+}
 
-    Ok(step_count.to_string())
+fn find_first_common_hit(cycles: &[GhostCycle]) -> Result<u64> {
+    let earliest_cycle_step = cycles.iter().map(|c| c.offset).max().unwrap_or(0).max(1);
+    if let Some(step_count) =
+        (1..earliest_cycle_step).find(|&step| cycles.iter().all(|c| c.is_hit(step)))
+    {
+        return Ok(step_count);
+    }
+
+    if let Some(cycle) = cycles.iter().find(|c| c.cycle_hits.is_empty()) {
+        bail!(
+            "Ghost starting at {} never reaches a node ending in 'Z' within its cycle",
+            cycle.start
+        );
+    }
+
+    // Usual input structure: every ghost passes a single Z node exactly once per cycle, at
+    // a step count equal to its cycle length.
+    if cycles.iter().all(|c| c.cycle_hits == [c.period]) {
+        let Some(step_count) = checked_lcm_of(cycles.iter().map(|c| c.period)) else {
+            bail!(
+                "Input does not have the assumed cycle structure: the common period of all ghosts exceeds u64"
+            );
+        };
+        return Ok(step_count);
+    }
+
+    let combination_count = cycles
+        .iter()
+        .try_fold(1usize, |acc, c| acc.checked_mul(c.cycle_hits.len()))
+        .filter(|&count| count <= MAX_HIT_COMBINATIONS)
+        .ok_or_else(|| {
+            anyhow!(
+                "Too many combinations of Z nodes to check (more than {})",
+                MAX_HIT_COMBINATIONS
+            )
+        })?;
+
+    let mut first_common_hit = None;
+    for hit_combination in cycles
+        .iter()
+        .map(|c| c.cycle_hits.iter())
+        .multi_cartesian_product()
+    {
+        let congruences = hit_combination
+            .iter()
+            .zip(cycles)
+            .map(|(&&hit, cycle)| (hit, cycle.period))
+            .collect_vec();
+
+        let Some((residue, modulus)) = crt(&congruences) else {
+            continue;
+        };
+
+        let step_count = if residue >= earliest_cycle_step {
+            residue
+        } else {
+            (earliest_cycle_step - residue)
+                .div_ceil(modulus)
+                .checked_mul(modulus)
+                .and_then(|offset| offset.checked_add(residue))
+                .ok_or_else(|| anyhow!("Step count of a common hit exceeds u64"))?
+        };
+
+        first_common_hit = Some(first_common_hit.map_or(step_count, |s: u64| s.min(step_count)));
+    }
+
+    first_common_hit.ok_or_else(|| {
+        anyhow!(
+            "Ghosts never reach nodes ending in 'Z' at the same time (checked {} combinations)",
+            combination_count
+        )
+    })
 }
 
 fn parse_input(input: &str) -> PuzzleInput {
@@ -119,7 +242,9 @@ fn parse_network(input: &str) -> HashMap<String, Edge> {
 
 #[cfg(test)]
 mod tests {
-    use crate::year_2023::day_08::{solve_part_one, solve_part_two, INPUT};
+    use crate::year_2023::day_08::{
+        find_first_common_hit, solve_part_one, solve_part_two, GhostCycle, INPUT,
+    };
 
     const EXAMPLE1_INPUT: &str = include_str!("example1.txt");
     const EXAMPLE2_INPUT: &str = include_str!("example2.txt");
@@ -149,9 +274,44 @@ mod tests {
         assert_eq!(result, "6");
     }
 
+    #[test]
+    fn part_two_reports_ghost_without_z_in_cycle() {
+        let input = "L\n\nAAA = (BBB, BBB)\nBBB = (BBB, BBB)\n11A = (11Z, 11Z)\n11Z = (11A, 11A)";
+        let err = solve_part_two(input).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Ghost starting at AAA never reaches a node ending in 'Z' within its cycle"
+        );
+    }
+
+    #[test]
+    fn part_two_offset_cycles_combined() {
+        let input = "L\n\n11A = (11B, 11B)\n11B = (11Z, 11Z)\n11Z = (11B, 11B)\n22A = (22Z, 22Z)\n22Z = (22B, 22B)\n22B = (22C, 22C)\n22C = (22Z, 22Z)";
+        let result = solve_part_two(input).unwrap();
+        assert_eq!(result, "4");
+    }
+
     #[test]
     fn part_two_real_input_correct() {
         let result = solve_part_two(INPUT).unwrap();
-        assert_eq!(result, "0");
+        assert_eq!(result, "12927600769609");
+    }
+
+    #[test]
+    fn part_two_reports_overflowing_common_period() {
+        let cycle = |start: &str, period: u64| GhostCycle {
+            start: start.to_string(),
+            offset: 1,
+            period,
+            prefix_hits: Vec::new(),
+            cycle_hits: vec![period],
+        };
+
+        let err = find_first_common_hit(&[cycle("11A", u64::MAX), cycle("22A", u64::MAX - 1)])
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Input does not have the assumed cycle structure: the common period of all ghosts exceeds u64"
+        );
     }
 }
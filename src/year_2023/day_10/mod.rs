@@ -5,7 +5,10 @@ use std::{
 
 use itertools::Itertools;
 
-use crate::{print_challenge_header, MyResult};
+use crate::{
+    common::{geometry::interior_lattice_points, math_2d::Point},
+    print_challenge_header, MyResult,
+};
 
 const INPUT: &str = include_str!("input.txt");
 
@@ -36,132 +39,40 @@ fn solve_part_two(input: &str) -> usize {
     let mut map = parse_input(input);
     let (start_pos, start_tile_type) = determine_start_tile(&map);
 
-    map.insert(start_pos.clone(), start_tile_type.clone());
-
-    let loop_tiles = get_loop_tiles(&map, Tile::new(start_pos, start_tile_type));
-    let double_horizontals = get_doubled_pipes(&map);
-
-    for tile in loop_tiles
-        .iter()
-        .filter(|t| !double_horizontals.contains(t))
-    {
-        map.remove(&tile.pos);
-    }
+    map.insert(start_pos.clone(), start_tile_type);
 
-    let tiles = map
-        .iter()
-        .map(|(pos, ttype)| Tile::new(pos.clone(), *ttype))
+    let vertices = get_loop_positions(&map, start_pos)
+        .into_iter()
+        .map(|pos| Point::new(i64::from(pos.x), i64::from(pos.y)))
         .collect_vec();
 
-    let mut external_tiles: HashSet<Tile> = HashSet::new();
-    let mut enclosed_tiles: HashSet<Tile> = HashSet::new();
-    let (max_x, max_y) = get_map_size(&map);
-
-    for tile in tiles {
-        if external_tiles.contains(&tile) || enclosed_tiles.contains(&tile) {
-            continue;
-        }
-
-        let reachable_tiles = traverse_breadth_first_enclosed_tiles(&map, &tile);
-
-        if reachable_tiles.iter().any(|t| {
-            t.pos.x == 0
-                || t.pos.y == 0
-                || t.pos.x == max_x
-                || t.pos.y == max_y
-                || external_tiles.contains(t)
-        }) {
-            external_tiles.extend(reachable_tiles);
-        } else {
-            enclosed_tiles.extend(reachable_tiles);
-        }
-    }
-
-    for doubled_horizontal in double_horizontals {
-        println!(
-            "removing horizontal: {:?}: {}",
-            doubled_horizontal,
-            enclosed_tiles.contains(&doubled_horizontal)
-        );
-        enclosed_tiles.remove(&doubled_horizontal);
-    }
-
-    print_map(&map, &enclosed_tiles, &external_tiles);
-
-    enclosed_tiles.len()
-}
-
-fn get_doubled_pipes(map: &HashMap<Position, TileType>) -> HashSet<Tile> {
-    let mut x = get_doubled_tile(map, TileType::Horizontal, TileType::Vertical);
-    let y = get_doubled_tile(map, TileType::Vertical, TileType::Horizontal);
-
-    x.extend(y);
-    x
-}
-
-fn get_doubled_tile(
-    map: &HashMap<Position, TileType>,
-    direction: TileType,
-    opposite_direction: TileType,
-) -> HashSet<Tile> {
-    map.iter()
-        .filter(|(_, &tt)| tt == direction)
-        .flat_map(|(pos, _)| {
-            opposite_direction
-                .get_adjacent_positions(pos, map)
-                .into_iter()
-                .filter(|p| *map.get(p).unwrap() == direction)
-        })
-        .map(|pos| Tile::new(pos.clone(), *map.get(&pos).unwrap()))
-        .collect::<HashSet<_>>()
+    interior_lattice_points(&vertices) as usize
 }
 
-fn traverse_breadth_first_enclosed_tiles(
-    map: &HashMap<Position, TileType>,
-    start: &Tile,
-) -> HashSet<Tile> {
-    let mut tiles_to_visit = HashSet::from([start.clone()]);
-    let mut visited_tiles = HashSet::new();
+fn get_loop_positions(map: &HashMap<Position, TileType>, start: Position) -> Vec<Position> {
+    let mut positions = vec![start.clone()];
+    let mut previous: Option<Position> = None;
+    let mut current = start.clone();
 
     loop {
-        let mut next_tiles = HashSet::new();
-        for tile in tiles_to_visit.iter() {
-            let neighbor_tiles = get_positions_for_enclosed_tiles(map, &tile.pos)
-                .into_iter()
-                .filter(|t| !visited_tiles.contains(t));
-            next_tiles.extend(neighbor_tiles);
-        }
-
-        visited_tiles.extend(tiles_to_visit.clone());
-        tiles_to_visit = next_tiles;
+        let next = map
+            .get(&current)
+            .unwrap()
+            .get_adjacent_positions(&current, map)
+            .into_iter()
+            .find(|pos| Some(pos) != previous.as_ref())
+            .unwrap();
 
-        if tiles_to_visit.is_empty() {
+        if next == start {
             break;
         }
-    }
 
-    visited_tiles
-}
-
-fn get_positions_for_enclosed_tiles(
-    map: &HashMap<Position, TileType>,
-    pos: &Position,
-) -> HashSet<Tile> {
-    // let offsets = [
-    //     (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1), (0, -1), (1, -1)
-    // ];
-    let mut offsets = vec![(1, 0), (0, 1), (-1, 0), (0, -1)];
-
-    if false {
-        offsets.push((-1, 1))
+        previous = Some(current);
+        current = next;
+        positions.push(current.clone());
     }
 
-    offsets
-        .iter()
-        .map(|(x, y)| Position::new(pos.x + x, pos.y + y))
-        .filter(|pos| map.contains_key(pos))
-        .map(|pos| Tile::new(pos.clone(), *map.get(&pos).unwrap()))
-        .collect::<HashSet<_>>()
+    positions
 }
 
 fn get_loop_tiles(map: &HashMap<Position, TileType>, start: Tile) -> HashSet<Tile> {
@@ -371,36 +282,6 @@ fn get_adjacent_tiles(tile: &Tile, map: &HashMap<Position, TileType>) -> Vec<Til
         .collect_vec()
 }
 
-fn get_map_size(map: &HashMap<Position, TileType>) -> (i32, i32) {
-    map.keys().map(|pos| (pos.x, pos.y)).max().unwrap()
-}
-
-fn print_map(
-    map: &HashMap<Position, TileType>,
-    enclosed_tiles: &HashSet<Tile>,
-    outclosed_tiles: &HashSet<Tile>,
-) {
-    let dimension = get_map_size(map);
-    for y in 0..=dimension.1 {
-        let mut line = Vec::new();
-        for x in 0..=dimension.0 {
-            let pos = Position::new(x, y);
-            if let Some(t) = map.get(&pos) {
-                if enclosed_tiles.iter().any(|t| t.pos == pos) {
-                    line.push('I');
-                } else if outclosed_tiles.iter().any(|t| t.pos == pos) {
-                    line.push('O');
-                } else {
-                    line.push(t.to_char());
-                }
-            } else {
-                line.push('#');
-            };
-        }
-        println!("{}", line.iter().collect::<String>());
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
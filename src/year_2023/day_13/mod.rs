@@ -4,7 +4,7 @@ use std::str::pattern::Pattern;
 
 use itertools::{Itertools, Position};
 
-use crate::{print_challenge_header, MyResult};
+use crate::{common::parsing::split_blocks, print_challenge_header, MyResult};
 
 const INPUT: &str = include_str!("input.txt");
 
@@ -109,7 +109,7 @@ fn replace_smudge(mut input: &str, index: usize) {
 
 fn parse_input(input: &str) -> Vec<ParsedPattern> {
     let mut patterns = Vec::new();
-    for pattern in input.split("\r\n\r\n") {
+    for pattern in split_blocks(input) {
         patterns.push(parse_pattern(pattern));
     }
 
@@ -4,7 +4,7 @@ mod day_03;
 mod day_04;
 pub mod day_05;
 mod day_06;
-mod day_07;
+pub(crate) mod day_07;
 mod day_08;
 mod day_09;
 mod day_10;
@@ -1,6 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
-use itertools::Itertools;
+use crate::common::parsing::split_blocks;
 
 use anyhow::{bail, Result};
 
@@ -30,8 +30,7 @@ struct PrintJob {
 }
 
 fn parse_input(input: &str) -> Result<PrintData> {
-    // TODO: find something better
-    let parts = input.split("\r\n\r\n").collect_vec();
+    let parts = split_blocks(input);
 
     if parts.len() != 2 {
         bail!("Invalid input: Expected 2 parts");
@@ -1,60 +1,50 @@
+use std::collections::HashMap;
+
 use macros::aoc_solver;
 
 use anyhow::Result;
 
+use crate::common::graph::{Graph, NodeId};
+
 use super::{is_job_correct, parse_input, Page, PageRules, PrintJob};
 
 #[aoc_solver(2024, 5, 2, super::INPUT)]
 fn solve(input: &str) -> Result<String> {
     let data = parse_input(input)?;
-    let result: u32 = data
-        .jobs
-        .iter()
-        .filter(|job| !is_job_correct(job, &data.rules))
-        .map(|job| correct_job(job, &data.rules))
-        .map(|pages| pages[pages.len() / 2].0)
-        .sum();
-    Ok(result.to_string())
-}
-
-fn correct_job(job: &PrintJob, rules: &PageRules) -> Vec<Page> {
-    merge_sort(&job.pages, rules)
-}
+    let mut result = 0;
+    for job in data.jobs.iter() {
+        if is_job_correct(job, &data.rules) {
+            continue;
+        }
 
-fn merge_sort(pages: &[Page], rules: &PageRules) -> Vec<Page> {
-    if pages.len() == 1 {
-        return pages.to_vec();
+        let pages = correct_job(job, &data.rules)?;
+        result += pages[pages.len() / 2].0;
     }
 
-    let middle = pages.len() / 2;
-    let left = merge_sort(&pages[..middle], rules);
-    let right = merge_sort(&pages[middle..], rules);
-
-    merge(&left, &right, rules)
+    Ok(result.to_string())
 }
 
-fn merge(left: &[Page], right: &[Page], rules: &PageRules) -> Vec<Page> {
-    let mut merged = Vec::new();
-    let mut left_idx = 0;
-    let mut right_idx = 0;
+// The complete rule set contains cycles, only the rules between pages of a job form a DAG
+fn correct_job(job: &PrintJob, rules: &PageRules) -> Result<Vec<Page>> {
+    let mut graph = Graph::new();
+    let ids: HashMap<Page, NodeId> = job
+        .pages
+        .iter()
+        .map(|&page| (page, graph.add_node(&page.0.to_string())))
+        .collect();
 
-    for _ in 0..(left.len() + right.len()) {
-        if right_idx < right.len()
-            && (left_idx >= left.len() || page_is_before(&right[right_idx], &left[left_idx], rules))
-        {
-            merged.push(right[right_idx]);
-            right_idx += 1;
-        } else {
-            merged.push(left[left_idx]);
-            left_idx += 1;
+    for (page, &from) in ids.iter() {
+        let Some(ordering) = rules.get(page) else {
+            continue;
+        };
+
+        for (_, &to) in ids.iter().filter(|(p, _)| ordering.pages_after.contains(p)) {
+            graph.connect(from, to);
         }
     }
 
-    merged
-}
-
-fn page_is_before(page: &Page, other: &Page, rules: &PageRules) -> bool {
-    rules.get(page).unwrap().pages_after.contains(other)
+    let pages: HashMap<NodeId, Page> = ids.into_iter().map(|(page, id)| (id, page)).collect();
+    Ok(graph.toposort()?.into_iter().map(|id| pages[&id]).collect())
 }
 
 #[cfg(test)]
@@ -4,6 +4,8 @@ use macros::aoc_solver;
 
 use anyhow::Result;
 
+use crate::common::cycle::brent;
+
 use super::{determine_guard_path, parse_input, Direction, Guard, Map, Point};
 
 #[aoc_solver(2024, 6, 2, super::INPUT)]
@@ -34,22 +36,12 @@ fn generate_loop_possitions(guard: Guard, map: &Map) -> HashSet<Point> {
 }
 
 fn check_is_loop(guard: Guard, map: &Map) -> bool {
-    let mut bi_grams: HashSet<Guard> = HashSet::new();
-
-    let mut current_guard = guard;
-    loop {
-        let Some(next_guard) = super::get_next_position(&current_guard, map) else {
-            return false;
-        };
+    // Leaving the map ends in `None`, which repeats with a cycle length of 1. A guard always
+    // moves or turns, so any longer cycle is a loop.
+    let step = |guard: &Option<Guard>| guard.and_then(|g| super::get_next_position(&g, map));
+    let cycle = brent(&Some(guard), step);
 
-        if bi_grams.contains(&next_guard) {
-            return true;
-        }
-
-        bi_grams.insert(next_guard);
-
-        current_guard = next_guard;
-    }
+    cycle.cycle_length > 1
 }
 
 #[allow(unused)]
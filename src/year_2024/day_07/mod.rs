@@ -3,6 +3,8 @@ use std::collections::HashSet;
 use itertools::Itertools;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
+use crate::common::number_theory::checked_concat_digits;
+
 use anyhow::{bail, Result};
 
 mod part_1;
@@ -70,7 +72,7 @@ fn apply_operator(a: u64, b: u64, operator: Operator) -> u64 {
     match operator {
         Operator::Add => a.checked_add(b).unwrap(),
         Operator::Multiply => a.checked_mul(b).unwrap(),
-        Operator::Concatenate => format!("{}{}", a, b).parse::<u64>().unwrap(),
+        Operator::Concatenate => checked_concat_digits(a, b).unwrap(),
     }
 }
 
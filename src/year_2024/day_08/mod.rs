@@ -1,8 +1,6 @@
-use std::{
-    collections::{HashMap, HashSet},
-    fmt::Debug,
-    ops::{Add, Mul, Sub},
-};
+use std::collections::{HashMap, HashSet};
+
+use crate::common::math_2d::Point;
 
 use anyhow::{anyhow, Result};
 
@@ -18,7 +16,7 @@ struct Dimensions {
 }
 
 impl Dimensions {
-    fn is_in_bounds(&self, point: Point2) -> bool {
+    fn is_in_bounds(&self, point: Point<i32>) -> bool {
         !(point.x < 0
             || point.y < 0
             || point.x as usize >= self.width
@@ -31,65 +29,7 @@ struct Frequency(char);
 
 struct Map {
     dim: Dimensions,
-    frequencies: HashMap<Frequency, HashSet<Point2>>,
-}
-
-#[derive(Clone, Copy, Eq, PartialEq, Hash)]
-struct Point2 {
-    x: i32,
-    y: i32,
-}
-
-impl Point2 {
-    fn new(x: i32, y: i32) -> Self {
-        Self { x, y }
-    }
-
-    fn to(self, other: Point2) -> Vec2 {
-        Vec2::new(other.x - self.x, other.y - self.y)
-    }
-}
-
-impl Debug for Point2 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!("({}, {})", self.x, self.y))
-    }
-}
-
-impl Add<Vec2> for Point2 {
-    type Output = Point2;
-
-    fn add(self, rhs: Vec2) -> Self::Output {
-        Point2::new(self.x + rhs.x, self.y + rhs.y)
-    }
-}
-
-impl Sub<Vec2> for Point2 {
-    type Output = Point2;
-
-    fn sub(self, rhs: Vec2) -> Self::Output {
-        Point2::new(self.x - rhs.x, self.y - rhs.y)
-    }
-}
-
-#[derive(Clone, Copy, Eq, PartialEq, Hash)]
-struct Vec2 {
-    x: i32,
-    y: i32,
-}
-
-impl Vec2 {
-    fn new(x: i32, y: i32) -> Self {
-        Self { x, y }
-    }
-}
-
-impl Mul<i32> for Vec2 {
-    type Output = Vec2;
-
-    fn mul(self, rhs: i32) -> Self::Output {
-        Vec2::new(self.x * rhs, self.y * rhs)
-    }
+    frequencies: HashMap<Frequency, HashSet<Point<i32>>>,
 }
 
 fn parse_map(input: &str) -> Result<Map> {
@@ -108,7 +48,7 @@ fn parse_map(input: &str) -> Result<Map> {
     Ok(map)
 }
 
-fn parse_freqency_nodes(input: &str) -> Result<HashMap<Frequency, HashSet<Point2>>> {
+fn parse_freqency_nodes(input: &str) -> Result<HashMap<Frequency, HashSet<Point<i32>>>> {
     let mut frequencies = HashMap::new();
 
     for (y, line) in input.lines().enumerate() {
@@ -123,7 +63,7 @@ fn parse_freqency_nodes(input: &str) -> Result<HashMap<Frequency, HashSet<Point2
             frequencies
                 .entry(Frequency(char))
                 .or_insert(HashSet::new())
-                .insert(Point2::new(x, y));
+                .insert(Point::new(x, y));
         }
     }
 
@@ -4,7 +4,7 @@ use macros::aoc_solver;
 
 use crate::year_2024::day_08::parse_map;
 
-use super::Point2;
+use crate::common::math_2d::Point;
 
 use anyhow::Result;
 
@@ -24,12 +24,12 @@ fn solve(input: &str) -> Result<String> {
     Ok(count.to_string())
 }
 
-fn find_antinodes(frequency_origins: &HashSet<Point2>) -> HashSet<Point2> {
+fn find_antinodes(frequency_origins: &HashSet<Point<i32>>) -> HashSet<Point<i32>> {
     let mut antinode_positions = HashSet::new();
     let pairs = find_node_pairs(frequency_origins);
 
     for (left, right) in pairs.iter().cloned() {
-        let vec = left.to(right);
+        let vec = right - left;
         antinode_positions.insert(right + vec);
         antinode_positions.insert(left - vec);
     }
@@ -37,7 +37,7 @@ fn find_antinodes(frequency_origins: &HashSet<Point2>) -> HashSet<Point2> {
     antinode_positions
 }
 
-fn find_node_pairs(points: &HashSet<Point2>) -> HashSet<(Point2, Point2)> {
+fn find_node_pairs(points: &HashSet<Point<i32>>) -> HashSet<(Point<i32>, Point<i32>)> {
     let points = Vec::from_iter(points.iter().cloned());
     let mut pairs = HashSet::new();
     for (idx, left) in points.iter().enumerate() {
@@ -6,7 +6,9 @@ use crate::year_2024::day_08::parse_map;
 
 use anyhow::Result;
 
-use super::{Dimensions, Point2, Vec2};
+use crate::common::math_2d::{Point, Vec2};
+
+use super::Dimensions;
 
 #[aoc_solver(2024, 8, 2, super::INPUT)]
 fn solve(input: &str) -> Result<String> {
@@ -21,20 +23,24 @@ fn solve(input: &str) -> Result<String> {
     Ok(count.to_string())
 }
 
-fn find_antinodes(frequency_origins: &HashSet<Point2>, dim: Dimensions) -> HashSet<Point2> {
+fn find_antinodes(frequency_origins: &HashSet<Point<i32>>, dim: Dimensions) -> HashSet<Point<i32>> {
     let mut antinode_positions = HashSet::new();
     let pairs = find_node_pairs(frequency_origins);
 
     for (left, right) in pairs.iter().cloned() {
-        let vec = left.to(right);
+        let vec = right - left;
         antinode_positions.extend(find_antinodes_on_line(right, vec, dim).iter());
-        antinode_positions.extend(find_antinodes_on_line(left, vec * -1, dim).iter());
+        antinode_positions.extend(find_antinodes_on_line(left, -vec, dim).iter());
     }
 
     antinode_positions
 }
 
-fn find_antinodes_on_line(origin: Point2, direction: Vec2, dim: Dimensions) -> HashSet<Point2> {
+fn find_antinodes_on_line(
+    origin: Point<i32>,
+    direction: Vec2<i32>,
+    dim: Dimensions,
+) -> HashSet<Point<i32>> {
     let mut points = HashSet::new();
     for scarlar in 0.. {
         let point = origin + direction * scarlar;
@@ -49,7 +55,7 @@ fn find_antinodes_on_line(origin: Point2, direction: Vec2, dim: Dimensions) -> H
     points
 }
 
-fn find_node_pairs(points: &HashSet<Point2>) -> HashSet<(Point2, Point2)> {
+fn find_node_pairs(points: &HashSet<Point<i32>>) -> HashSet<(Point<i32>, Point<i32>)> {
     let points = Vec::from_iter(points.iter().cloned());
     let mut pairs = HashSet::new();
     for (idx, left) in points.iter().enumerate() {
@@ -1,5 +1,7 @@
 use itertools::Itertools;
 
+use crate::common::number_theory::{count_digits, split_digits};
+
 use anyhow::Result;
 
 mod part_1;
@@ -32,13 +34,10 @@ fn apply_rules(value: u64) -> Vec<u64> {
         return vec![1];
     }
 
-    let str_value = value.to_string();
-    if str_value.len() % 2 == 0 {
-        let middle = str_value.len() / 2;
-        return vec![
-            str_value[..middle].parse::<u64>().unwrap(),
-            str_value[middle..].parse::<u64>().unwrap(),
-        ];
+    let digit_count = count_digits(value);
+    if digit_count.is_multiple_of(2) {
+        let (left, right) = split_digits(value, digit_count / 2);
+        return vec![left, right];
     }
 
     vec![value * 2024]
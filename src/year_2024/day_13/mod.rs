@@ -1,25 +1,23 @@
 use itertools::Itertools;
-use once_cell::sync::Lazy;
-use regex::Regex;
 
-use anyhow::{anyhow, bail, Result};
+use crate::common::{
+    linalg::solve_linear_system,
+    math_2d::Vec2,
+    parsing::{parse_integers, split_blocks},
+};
+
+use anyhow::{bail, Result};
 
 mod part_1;
 mod part_2;
 
 const INPUT: &str = include_str!("input.txt");
 
-#[derive(Clone, Copy)]
-struct Vec2 {
-    x: i64,
-    y: i64,
-}
-
 #[derive(Clone, Copy)]
 struct Machine {
-    button_a: Vec2,
-    button_b: Vec2,
-    price: Vec2,
+    button_a: Vec2<i64>,
+    button_b: Vec2<i64>,
+    price: Vec2<i64>,
 }
 
 fn solve_for_input(input: &str, offset: i64) -> Result<String> {
@@ -28,7 +26,10 @@ fn solve_for_input(input: &str, offset: i64) -> Result<String> {
         .iter()
         .map(|m| apply_offset(*m, offset))
         .collect_vec();
-    let sum: u64 = machines.iter().map(|m| compute_required_token(*m)).sum();
+    let sum = machines
+        .iter()
+        .map(|m| compute_required_token(*m))
+        .sum::<Result<u64>>()?;
 
     Ok(sum.to_string())
 }
@@ -45,8 +46,8 @@ fn apply_offset(machine: Machine, offset: i64) -> Machine {
 }
 
 fn parse_input(input: &str) -> Result<Vec<Machine>> {
-    input
-        .split("\r\n\r\n")
+    split_blocks(input)
+        .into_iter()
         .map(parse_machine)
         .collect::<Result<Vec<_>, _>>()
 }
@@ -66,68 +67,63 @@ fn parse_machine(block: &str) -> Result<Machine> {
     Ok(machine)
 }
 
-fn parse_price(line: &str) -> Result<Vec2> {
-    static PRICE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"Prize: X=(\d+), Y=(\d+)").unwrap());
-    let capture = PRICE_REGEX
-        .captures(line)
-        .ok_or(anyhow!("Invalid a button input"))?;
-    let price = Vec2 {
-        x: capture[1].parse::<i64>()?,
-        y: capture[2].parse::<i64>()?,
-    };
+fn parse_price(line: &str) -> Result<Vec2<i64>> {
+    if !line.starts_with("Prize:") {
+        bail!("Invalid price input: {}", line);
+    }
 
-    Ok(price)
+    parse_vec(line)
 }
 
-fn parse_button(line: &str) -> Result<Vec2> {
-    static BUTTON_REGEX: Lazy<Regex> =
-        Lazy::new(|| Regex::new(r"Button [AB]: X\+(\d+), Y\+(\d+)").unwrap());
-
-    let capture = BUTTON_REGEX
-        .captures(line)
-        .ok_or(anyhow!("Invalid a button input"))?;
-    let btn = Vec2 {
-        x: capture[1].parse::<i64>()?,
-        y: capture[2].parse::<i64>()?,
-    };
+fn parse_button(line: &str) -> Result<Vec2<i64>> {
+    if !line.starts_with("Button") {
+        bail!("Invalid button input: {}", line);
+    }
 
-    Ok(btn)
+    parse_vec(line)
 }
-fn compute_required_token(machine: Machine) -> u64 {
-    let (token_a, token_b) = find_tokens(machine);
 
-    if are_tokens_correct(token_a, token_b, machine) {
-        return (token_a * 3 + token_b).try_into().unwrap();
+fn parse_vec(line: &str) -> Result<Vec2<i64>> {
+    let values = parse_integers::<i64>(line)?;
+    if values.len() != 2 {
+        bail!("Expected an X and Y value in: {}", line);
     }
 
-    0
+    Ok(Vec2 {
+        x: values[0],
+        y: values[1],
+    })
 }
 
-fn find_tokens(machine: Machine) -> (i64, i64) {
-    let token_b = compute_b_tokens(machine);
-    let token_a = compute_a_tokens(token_b, machine);
-
-    (token_a, token_b)
+fn compute_required_token(machine: Machine) -> Result<u64> {
+    let tokens = find_tokens(machine)?.map_or(0, |(token_a, token_b)| token_a * 3 + token_b);
+    Ok(tokens)
 }
 
-fn are_tokens_correct(token_a: i64, token_b: i64, machine: Machine) -> bool {
-    let p = machine.price;
+fn find_tokens(machine: Machine) -> Result<Option<(u64, u64)>> {
     let a = machine.button_a;
     let b = machine.button_b;
+    let p = machine.price;
 
-    p.x == token_a * a.x + token_b * b.x && p.y == token_a * a.y + token_b * b.y
-}
+    let Some(space) = solve_linear_system(&[vec![a.x, b.x], vec![a.y, b.y]], &[p.x, p.y])? else {
+        return Ok(None);
+    };
+
+    let upper_bounds = [max_presses(a, p), max_presses(b, p)];
+    let tokens = space
+        .minimize(&[3, 1], &upper_bounds)?
+        .map(|(_, presses)| (presses[0], presses[1]));
 
-fn compute_a_tokens(b_token: i64, machine: Machine) -> i64 {
-    (machine.price.y - b_token * machine.button_b.y) / machine.button_a.y
+    Ok(tokens)
 }
 
-fn compute_b_tokens(machine: Machine) -> i64 {
-    let price = machine.price;
-    let a_btn = machine.button_a;
-    let b_btn = machine.button_b;
-    let upper = price.x * a_btn.y - price.y * a_btn.x;
-    let lower = b_btn.x * a_btn.y - b_btn.y * a_btn.x;
+fn max_presses(button: Vec2<i64>, price: Vec2<i64>) -> u64 {
+    let presses_x = (button.x > 0).then(|| price.x / button.x);
+    let presses_y = (button.y > 0).then(|| price.y / button.y);
 
-    upper / lower
+    presses_x
+        .into_iter()
+        .chain(presses_y)
+        .min()
+        .map_or(0, |presses| presses.max(0) as u64)
 }
@@ -1,9 +1,10 @@
-use once_cell::sync::Lazy;
-use regex::Regex;
+use crate::common::{
+    math_2d::{Dimensions, Point, Vec2},
+    number_theory::modulo,
+    parsing::{parse_integers, parse_lines},
+};
 
-use crate::common::math_2d::{Dimensions, Point, Vec2};
-
-use anyhow::{anyhow, Result};
+use anyhow::{bail, Result};
 
 mod part_1;
 mod part_2;
@@ -17,19 +18,17 @@ struct Robot {
 }
 
 fn parse_input(input: &str) -> Result<Vec<Robot>> {
-    input
-        .lines()
-        .map(parse_robot)
-        .collect::<Result<Vec<_>, _>>()
+    parse_lines(input, parse_robot)
 }
 
 fn parse_robot(line: &str) -> Result<Robot> {
-    static REGEX: Lazy<Regex> =
-        Lazy::new(|| Regex::new(r"p=(\d+),(\d+) v=(-?\d+),(-?\d+)").unwrap());
+    let values = parse_integers::<i64>(line)?;
+    if values.len() != 4 {
+        bail!("Expected position and velocity in line: {}", line);
+    }
 
-    let caputres = REGEX.captures(line).ok_or(anyhow!("Invalid line"))?;
-    let pos = Point::new(caputres[1].parse::<u64>()?, caputres[2].parse::<u64>()?);
-    let vel = Vec2::new(caputres[3].parse::<i32>()?, caputres[4].parse::<i32>()?);
+    let pos = Point::new(values[0].try_into()?, values[1].try_into()?);
+    let vel = Vec2::new(values[2].try_into()?, values[3].try_into()?);
 
     Ok(Robot { pos, velocity: vel })
 }
@@ -41,33 +40,22 @@ fn move_robot(robot: Robot, dim: Dimensions, times: u64) -> Result<Point<u64>> {
     Ok(Point::new(next_pos.x % dim.width, next_pos.y % dim.height))
 }
 
+fn step_robot(robot: Robot, dim: Dimensions) -> Robot {
+    let next_pos = robot.pos + normalize_velocity(robot.velocity, dim);
+
+    Robot {
+        pos: Point::new(next_pos.x % dim.width, next_pos.y % dim.height),
+        velocity: robot.velocity,
+    }
+}
+
 fn normalize_velocity(velocity: Vec2<i32>, dim: Dimensions) -> Vec2<u64> {
     Vec2::new(
-        normalize_value(velocity.x, dim.width),
-        normalize_value(velocity.y, dim.height),
+        modulo(velocity.x.into(), dim.width),
+        modulo(velocity.y.into(), dim.height),
     )
 }
 
-fn normalize_value(value: i32, identity: u64) -> u64 {
-    if value < 0 {
-        let abs: u64 = value.unsigned_abs().into();
-        let times = abs / identity;
-        let rem = abs % identity;
-
-        if abs >= identity {
-            println!(
-                "normalized {} to {}",
-                value,
-                identity * times + identity - rem
-            );
-        }
-
-        identity * times + identity - rem
-    } else {
-        value as u64
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use crate::year_2024::day_14::{move_robot, Dimensions, Point, Robot, Vec2};
@@ -1,10 +1,14 @@
-use std::{collections::HashSet, io::stdin};
+use std::collections::HashSet;
 
 use macros::aoc_solver;
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 
-use super::{move_robot, parse_input, Dimensions, Point, Robot};
+use crate::common::cycle::brent;
+
+use super::{move_robot, parse_input, step_robot, Dimensions, Point, Robot};
+
+const FRAME_LINE_LENGTH: u64 = 10;
 
 #[aoc_solver(2024, 14, 2, super::INPUT)]
 fn solve(input: &str) -> Result<String> {
@@ -14,29 +18,27 @@ fn solve(input: &str) -> Result<String> {
     };
 
     let robots = parse_input(input)?;
-    find_christmas_tree(&robots, dim)?;
+    let times = find_christmas_tree(&robots, dim)?;
 
-    Ok(0.to_string())
+    Ok(times.to_string())
 }
 
-fn find_christmas_tree(robots: &[Robot], dim: Dimensions) -> Result<()> {
-    for times in 0.. {
-        if times % 1000 == 0 {
-            println!("iteration {}", times);
-        }
+fn find_christmas_tree(robots: &[Robot], dim: Dimensions) -> Result<u64> {
+    let step = |robots: &Vec<Robot>| robots.iter().map(|r| step_robot(*r, dim)).collect();
+    let cycle = brent(&robots.to_vec(), step);
+    let distinct_states = u64::try_from(cycle.prefix_length + cycle.cycle_length)?;
 
+    for times in 0..distinct_states {
         let positions = get_robot_positions(robots, dim, times)?;
-        if !is_likely_a_christmas_tree(&positions) {
-            continue;
+        if is_likely_a_christmas_tree(&positions) {
+            return Ok(times);
         }
-
-        print_positions(&positions, dim);
-        println!("Iteration {}", times);
-
-        stdin().read_line(&mut String::new()).unwrap();
     }
 
-    Ok(())
+    bail!(
+        "The robots never form a christmas tree within the {} steps before they repeat",
+        distinct_states
+    )
 }
 
 fn get_robot_positions(
@@ -50,54 +52,51 @@ fn get_robot_positions(
         .collect::<Result<HashSet<_>, _>>()
 }
 
-fn print_positions(pos: &HashSet<Point<u64>>, dim: Dimensions) {
-    for y in 0..dim.height {
-        let line = (0..dim.width)
-            .map(|x| {
-                if pos.contains(&Point { x, y }) {
-                    "#"
-                } else {
-                    "."
-                }
-            })
-            .collect::<String>();
-        println!("{}", line);
-    }
-}
-
+// The tree is drawn inside a frame, so look for a long horizontal line of robots
 fn is_likely_a_christmas_tree(positions: &HashSet<Point<u64>>) -> bool {
-    let vertical_center_count = positions
-        .iter()
-        .filter(|pos| pos.y > 43 && pos.y < 77)
-        .count() as f64;
-
-    let horizontal_center_count = positions
-        .iter()
-        .filter(|pos| pos.x > 30 && pos.x < 70)
-        .count() as f64;
-
-    let limit = positions.len() as f64 * 0.7;
-
-    limit <= vertical_center_count || limit <= horizontal_center_count
+    positions.iter().any(|pos| {
+        (1..FRAME_LINE_LENGTH).all(|offset| positions.contains(&Point::new(pos.x + offset, pos.y)))
+    })
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::year_2024::day_14::{parse_input, Dimensions};
+    use std::collections::HashSet;
+
+    use crate::year_2024::day_14::{parse_input, Dimensions, Point};
 
-    use super::{get_robot_positions, print_positions};
+    use super::get_robot_positions;
 
     #[test]
-    fn test() {
-        // TODO: setup test
+    fn robot_positions_after_100_seconds() {
         let dim = Dimensions {
             width: 11,
             height: 7,
         };
-        let r = parse_input(include_str!("example.txt")).unwrap();
-        let pos = get_robot_positions(&r, dim, 100).unwrap();
-        print_positions(&pos, dim);
+        let robots = parse_input(include_str!("example.txt")).unwrap();
+        let positions = get_robot_positions(&robots, dim, 100).unwrap();
+
+        let expected = [
+            (6, 0),
+            (9, 0),
+            (0, 2),
+            (1, 3),
+            (2, 3),
+            (5, 4),
+            (3, 5),
+            (4, 5),
+            (1, 6),
+            (6, 6),
+        ]
+        .into_iter()
+        .map(|(x, y)| Point::new(x, y))
+        .collect::<HashSet<_>>();
+        assert_eq!(positions, expected);
+    }
 
-        assert_eq!(0, 1);
+    #[test]
+    fn solve_input() {
+        let result = super::solve(crate::year_2024::day_14::INPUT).unwrap();
+        assert_eq!(result, "7858");
     }
 }
@@ -1,10 +1,9 @@
 use crate::common::{
     math_2d::{Grid, Point, PointIdx, Vec2},
-    parsing::parse_grid,
+    parsing::{parse_grid, split_blocks},
 };
 
 use anyhow::{bail, Result};
-use itertools::Itertools;
 
 mod part_1;
 mod part_2;
@@ -38,7 +37,7 @@ impl Tile {
 }
 
 fn parse_input(input: &str) -> Result<Map<Tile>> {
-    let blocks = input.split("\r\n\r\n").collect_vec();
+    let blocks = split_blocks(input);
 
     if blocks.len() != 2 {
         bail!(
@@ -22,7 +22,7 @@ enum DoubleTile {
 #[aoc_solver(2024, 15, 2, super::INPUT)]
 fn solve(input: &str) -> Result<String> {
     let map = parse_input(input)?;
-    let grid = enlarge_map(&map.grid)?;
+    let grid = enlarge_map(&map.grid);
     let mut map = Map {
         directions: map.directions,
         robot_pos: find_tile_position(&grid, DoubleTile::Robot).unwrap(),
@@ -146,31 +146,13 @@ fn get_horizontal_points_to_move(
     points
 }
 
-fn enlarge_map(grid: &Grid<Tile>) -> Result<Grid<DoubleTile>> {
-    let mut rows: Vec<Vec<DoubleTile>> = Vec::new();
-
-    for y in 0..grid.dim().height {
-        let mut current_row = Vec::new();
-        for x in 0..grid.dim().width {
-            let point = Point::new(x, y);
-            let tile = *grid.get_at(point).unwrap();
-            current_row.push(match tile {
-                Tile::Empty => DoubleTile::Empty,
-                Tile::Wall => DoubleTile::Wall,
-                Tile::Robot => DoubleTile::Robot,
-                Tile::Box => DoubleTile::LeftBox,
-            });
-            current_row.push(match tile {
-                Tile::Empty => DoubleTile::Empty,
-                Tile::Wall => DoubleTile::Wall,
-                Tile::Robot => DoubleTile::Empty,
-                Tile::Box => DoubleTile::RightBox,
-            });
-        }
-        rows.push(current_row);
-    }
-
-    Grid::from_raw_values(rows)
+fn enlarge_map(grid: &Grid<Tile>) -> Grid<DoubleTile> {
+    grid.widen(|tile| match tile {
+        Tile::Empty => [DoubleTile::Empty, DoubleTile::Empty],
+        Tile::Wall => [DoubleTile::Wall, DoubleTile::Wall],
+        Tile::Robot => [DoubleTile::Robot, DoubleTile::Empty],
+        Tile::Box => [DoubleTile::LeftBox, DoubleTile::RightBox],
+    })
 }
 
 #[allow(dead_code)]
@@ -176,13 +176,7 @@ fn get_next_points(grid: &Grid<Tile>, pos: Point<i32>, current_dir: Vec2<i32>) -
 }
 
 fn get_next_dir(dir: Vec2<i32>) -> (Vec2<i32>, Vec2<i32>) {
-    match dir {
-        Vec2::RIGHT => (Vec2::UP, Vec2::DOWN),
-        Vec2::UP => (Vec2::LEFT, Vec2::RIGHT),
-        Vec2::LEFT => (Vec2::DOWN, Vec2::UP),
-        Vec2::DOWN => (Vec2::RIGHT, Vec2::LEFT),
-        _ => panic!("invalid direction"),
-    }
+    (dir.rotate_counter_clockwise(), dir.rotate_clockwise())
 }
 
 fn map_char_to_tile(char: char) -> Result<Tile> {
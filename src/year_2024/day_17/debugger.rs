@@ -0,0 +1,139 @@
+use itertools::Itertools;
+
+use anyhow::{bail, Result};
+
+use crate::common::vm::StopReason;
+
+use super::{parse_input, Instruction};
+
+// Programs from valid inputs halt quickly, this only guards against endless loops
+const TRACE_STEP_LIMIT: usize = 10_000;
+
+pub(crate) fn chrono_disasm(input: &str, trace: bool, breakpoints: &[usize]) -> Result<String> {
+    let rom = parse_input(input)?;
+    let mut lines = vec![
+        format!("Register A: {}", rom.reg_a),
+        format!("Register B: {}", rom.reg_b),
+        format!("Register C: {}", rom.reg_c),
+        String::new(),
+        disassemble(&rom.program)?,
+    ];
+
+    if !trace && breakpoints.is_empty() {
+        return Ok(lines.join("\n"));
+    }
+
+    let mut emu = rom.boot().with_step_limit(TRACE_STEP_LIMIT);
+    emu.enable_trace();
+    for &pc in breakpoints {
+        emu.add_breakpoint(pc);
+    }
+
+    lines.push(String::new());
+    let mut logged_steps = 0;
+    loop {
+        let stop = emu.resume()?;
+        if trace {
+            lines.extend(emu.trace()[logged_steps..].iter().map(|e| e.to_string()));
+            logged_steps = emu.trace().len();
+        }
+
+        match stop {
+            StopReason::Halted => break,
+            StopReason::StepLimit => {
+                lines.push(format!("Stopped after {} steps", TRACE_STEP_LIMIT));
+                break;
+            }
+            StopReason::Breakpoint(pc) => lines.push(format!(
+                "Breakpoint at pc={:02}: {} output={}",
+                pc,
+                emu.machine().registers,
+                emu.machine().output.iter().join(",")
+            )),
+        }
+    }
+
+    lines.push(format!("Output: {}", emu.machine().output.iter().join(",")));
+
+    Ok(lines.join("\n"))
+}
+
+fn disassemble(program: &[u8]) -> Result<String> {
+    if !program.len().is_multiple_of(2) {
+        bail!(
+            "Program has an odd number of values, the last instruction has no operand: {}",
+            program.len()
+        );
+    }
+
+    program
+        .chunks(2)
+        .enumerate()
+        .map(|(idx, raw)| {
+            let instruction = Instruction::try_from_u8(raw[0], raw[1])?;
+            Ok(format!(
+                "{:02}  {},{}  {}",
+                idx * 2,
+                raw[0],
+                raw[1],
+                instruction
+            ))
+        })
+        .collect::<Result<Vec<_>>>()
+        .map(|lines| lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{chrono_disasm, disassemble};
+
+    #[test]
+    fn disassemble_input() {
+        let listing = disassemble(&[2, 4, 1, 1, 7, 5, 4, 7, 1, 4, 0, 3, 5, 5, 3, 0]).unwrap();
+        assert_eq!(
+            listing,
+            [
+                "00  2,4  bst A",
+                "02  1,1  bxl 1",
+                "04  7,5  cdv B",
+                "06  4,7  bxc",
+                "08  1,4  bxl 4",
+                "10  0,3  adv 3",
+                "12  5,5  out B",
+                "14  3,0  jnz 0",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn reject_invalid_programs() {
+        assert!(disassemble(&[0, 1, 5]).is_err());
+        assert!(disassemble(&[5, 7]).is_err());
+    }
+
+    #[test]
+    fn trace_example() {
+        let output = chrono_disasm(include_str!("example_part_2.txt"), true, &[]).unwrap();
+        assert!(output.starts_with("Register A: 2024\n"));
+        assert!(output.contains("\n00  0,3  adv 3\n"));
+        assert!(output.contains("\npc=02 out A  A=253 B=0 C=0\n"));
+        assert!(output.ends_with("\nOutput: 5,7,3,0"));
+    }
+
+    #[test]
+    fn breakpoints_without_trace() {
+        let output = chrono_disasm(include_str!("example_part_2.txt"), false, &[4]).unwrap();
+        let lines = output.lines().skip(8).collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                "Breakpoint at pc=04: A=253 B=0 C=0 output=5",
+                "Breakpoint at pc=04: A=31 B=0 C=0 output=5,7",
+                "Breakpoint at pc=04: A=3 B=0 C=0 output=5,7,3",
+                "Breakpoint at pc=04: A=0 B=0 C=0 output=5,7,3,0",
+                "Output: 5,7,3,0",
+            ]
+        );
+    }
+}
@@ -1,11 +1,20 @@
+use std::fmt::Display;
+
 use anyhow::{anyhow, bail, Result};
-use itertools::Itertools;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
+use crate::common::{
+    parsing::split_blocks,
+    vm::{Machine, RegisterFile, Vm},
+};
+
+mod debugger;
 mod part_1;
 mod part_2;
 
+pub(crate) use debugger::chrono_disasm;
+
 const INPUT: &str = include_str!("input.txt");
 
 #[derive(Debug, Clone, Copy)]
@@ -15,6 +24,27 @@ enum Register {
     RegC,
 }
 
+impl Register {
+    fn index(self) -> usize {
+        match self {
+            Register::RegA => 0,
+            Register::RegB => 1,
+            Register::RegC => 2,
+        }
+    }
+}
+
+impl Display for Register {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Register::RegA => "A",
+            Register::RegB => "B",
+            Register::RegC => "C",
+        };
+        write!(f, "{}", name)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 struct LiteralOp {
     value: u8,
@@ -41,6 +71,15 @@ impl ComboOp {
     }
 }
 
+impl Display for ComboOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ComboOp::Value(value) => write!(f, "{}", value),
+            ComboOp::RegisterValue(register) => write!(f, "{}", register),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 enum Instruction {
     Div { target: Register, operand: ComboOp },
@@ -77,100 +116,134 @@ impl Instruction {
 
         Ok(res)
     }
+
+    fn mnemonic(&self) -> &'static str {
+        match self {
+            Instruction::Div {
+                target: Register::RegA,
+                ..
+            } => "adv",
+            Instruction::Div {
+                target: Register::RegB,
+                ..
+            } => "bdv",
+            Instruction::Div {
+                target: Register::RegC,
+                ..
+            } => "cdv",
+            Instruction::Bxl(_) => "bxl",
+            Instruction::Bst(_) => "bst",
+            Instruction::Jnz(_) => "jnz",
+            Instruction::Bxc => "bxc",
+            Instruction::Out(_) => "out",
+        }
+    }
 }
 
-#[derive(Clone)]
-struct Rom {
-    reg_a: u128,
-    reg_b: u128,
-    reg_c: u128,
-    program: Vec<u8>,
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mnemonic = self.mnemonic();
+        match self {
+            Instruction::Div { operand, .. } => write!(f, "{} {}", mnemonic, operand),
+            Instruction::Bxl(operand) | Instruction::Jnz(operand) => {
+                write!(f, "{} {}", mnemonic, operand.value)
+            }
+            Instruction::Bst(operand) | Instruction::Out(operand) => {
+                write!(f, "{} {}", mnemonic, operand)
+            }
+            Instruction::Bxc => write!(f, "{}", mnemonic),
+        }
+    }
 }
 
-struct Emulator {
-    pc: usize,
+#[derive(Clone)]
+struct Rom {
     reg_a: u128,
     reg_b: u128,
     reg_c: u128,
     program: Vec<u8>,
-    output: Vec<u8>,
 }
 
-impl Emulator {
-    fn from_rom(rom: Rom) -> Self {
-        Self {
-            pc: 0,
-            reg_a: rom.reg_a,
-            reg_b: rom.reg_b,
-            reg_c: rom.reg_c,
-            program: rom.program,
+impl Rom {
+    fn boot(self) -> Emulator {
+        let computer = Computer {
+            registers: RegisterFile::new(["A", "B", "C"], [self.reg_a, self.reg_b, self.reg_c]),
             output: Vec::new(),
-        }
+        };
+        Vm::new(computer, self.program)
     }
+}
 
-    fn run(&mut self) -> Result<Vec<u8>> {
-        loop {
-            if self.pc >= self.program.len() {
-                break;
-            }
-            let instruction = self.read_instruction()?;
-            self.pc += 2;
+type Emulator = Vm<Computer>;
 
-            self.handle_instruction(instruction);
-        }
+struct Computer {
+    registers: RegisterFile<u128, 3>,
+    output: Vec<u8>,
+}
 
-        Ok(self.output.clone())
-    }
+impl Machine for Computer {
+    type Word = u8;
+    type Instruction = Instruction;
+    type Registers = RegisterFile<u128, 3>;
 
-    fn read_instruction(&self) -> Result<Instruction> {
-        let raw_inst = self.program[self.pc];
-        let raw_op = self.program[self.pc + 1];
+    fn decode(program: &[u8], pc: usize) -> Result<(Instruction, usize)> {
+        let raw_inst = program[pc];
+        let Some(&raw_op) = program.get(pc + 1) else {
+            bail!("Missing operand for instruction at {}", pc);
+        };
 
-        Instruction::try_from_u8(raw_inst, raw_op)
+        Ok((Instruction::try_from_u8(raw_inst, raw_op)?, 2))
     }
 
-    fn handle_instruction(&mut self, instruction: Instruction) {
+    fn execute(&mut self, instruction: Instruction) -> Result<Option<usize>> {
         match instruction {
             Instruction::Div { target, operand } => self.handle_div(target, operand),
             Instruction::Bxl(operand) => self.bxl(operand),
             Instruction::Bst(operand) => self.bst(operand),
-            Instruction::Jnz(operand) => self.jnz(operand),
+            Instruction::Jnz(operand) => return Ok(self.jnz(operand)),
             Instruction::Bxc => self.bxc(),
             Instruction::Out(operand) => self.out(operand),
         }
+
+        Ok(None)
     }
 
+    fn registers(&self) -> &RegisterFile<u128, 3> {
+        &self.registers
+    }
+}
+
+impl Computer {
     fn handle_div(&mut self, target: Register, operand: ComboOp) {
-        let op_value = self.get_operand_value(operand);
-        let denominator = 2_u128.pow(op_value.try_into().unwrap());
-        let value = self.reg_a / denominator;
-        match target {
-            Register::RegA => self.reg_a = value,
-            Register::RegB => self.reg_b = value,
-            Register::RegC => self.reg_c = value,
-        }
+        // Shifting out every bit leaves zero, just like dividing by a huge power of two
+        let shift = u32::try_from(self.get_operand_value(operand)).unwrap_or(u32::MAX);
+        let value = self
+            .register(Register::RegA)
+            .checked_shr(shift)
+            .unwrap_or(0);
+        self.registers.set(target.index(), value);
     }
 
     fn bxl(&mut self, op: LiteralOp) {
-        let op_value = op.value as u128;
-        self.reg_b ^= op_value;
+        *self.registers.get_mut(Register::RegB.index()) ^= op.value as u128;
     }
 
     fn bst(&mut self, op: ComboOp) {
         let op_value = self.get_operand_value(op);
-        self.reg_b = op_value % 8;
+        self.registers.set(Register::RegB.index(), op_value % 8);
     }
 
-    fn jnz(&mut self, op: LiteralOp) {
-        if self.reg_a == 0 {
-            return;
+    fn jnz(&mut self, op: LiteralOp) -> Option<usize> {
+        if self.register(Register::RegA) == 0 {
+            return None;
         }
 
-        self.pc = op.value as usize;
+        Some(op.value as usize)
     }
 
     fn bxc(&mut self) {
-        self.reg_b ^= self.reg_c;
+        let reg_c = self.register(Register::RegC);
+        *self.registers.get_mut(Register::RegB.index()) ^= reg_c;
     }
 
     fn out(&mut self, op: ComboOp) {
@@ -179,24 +252,20 @@ impl Emulator {
         self.output.push(res as u8);
     }
 
-    fn get_register(&self, reg: Register) -> u128 {
-        match reg {
-            Register::RegA => self.reg_a,
-            Register::RegB => self.reg_b,
-            Register::RegC => self.reg_c,
-        }
+    fn register(&self, reg: Register) -> u128 {
+        self.registers.get(reg.index())
     }
 
     fn get_operand_value(&self, op: ComboOp) -> u128 {
         match op {
             ComboOp::Value(i) => i as u128,
-            ComboOp::RegisterValue(register) => self.get_register(register),
+            ComboOp::RegisterValue(register) => self.register(register),
         }
     }
 }
 
 fn parse_input(input: &str) -> Result<Rom> {
-    let blocks = input.split("\r\n\r\n").collect_vec();
+    let blocks = split_blocks(input);
     if blocks.len() != 2 {
         bail!("exepcted 2 blocks");
     }
@@ -238,7 +307,9 @@ fn parse_program(line: &str) -> Result<Vec<u8>> {
 
 #[cfg(test)]
 mod tests {
-    use super::{Emulator, Rom};
+    use crate::common::vm::StopReason;
+
+    use super::{Register, Rom};
 
     #[test]
     fn test_bst_instruction() {
@@ -248,9 +319,9 @@ mod tests {
             reg_c: 9,
             program: vec![2, 6],
         };
-        let mut emu = Emulator::from_rom(rom);
+        let mut emu = rom.boot();
         emu.run().unwrap();
-        assert_eq!(emu.reg_b, 1)
+        assert_eq!(emu.machine().register(Register::RegB), 1)
     }
 
     #[test]
@@ -261,9 +332,9 @@ mod tests {
             reg_c: 0,
             program: vec![1, 7],
         };
-        let mut emu = Emulator::from_rom(rom);
+        let mut emu = rom.boot();
         emu.run().unwrap();
-        assert_eq!(emu.reg_b, 26)
+        assert_eq!(emu.machine().register(Register::RegB), 26)
     }
 
     #[test]
@@ -274,9 +345,9 @@ mod tests {
             reg_c: 43690,
             program: vec![4, 0],
         };
-        let mut emu = Emulator::from_rom(rom);
+        let mut emu = rom.boot();
         emu.run().unwrap();
-        assert_eq!(emu.reg_b, 44354)
+        assert_eq!(emu.machine().register(Register::RegB), 44354)
     }
 
     #[test]
@@ -287,9 +358,9 @@ mod tests {
             reg_c: 0,
             program: vec![5, 0, 5, 1, 5, 4],
         };
-        let mut emu = Emulator::from_rom(rom);
-        let out = emu.run().unwrap();
-        assert_eq!(out, [0, 1, 2]);
+        let mut emu = rom.boot();
+        emu.run().unwrap();
+        assert_eq!(emu.machine().output, [0, 1, 2]);
     }
 
     #[test]
@@ -300,10 +371,53 @@ mod tests {
             reg_c: 0,
             program: vec![0, 1, 5, 4, 3, 0],
         };
-        let mut emu = Emulator::from_rom(rom);
-        let out = emu.run().unwrap();
-        assert_eq!(out, [4, 2, 5, 6, 7, 7, 7, 7, 3, 1, 0]);
-        assert_eq!(emu.reg_a, 0);
+        let mut emu = rom.boot();
+        emu.run().unwrap();
+        assert_eq!(emu.machine().output, [4, 2, 5, 6, 7, 7, 7, 7, 3, 1, 0]);
+        assert_eq!(emu.machine().register(Register::RegA), 0);
+    }
+
+    #[test]
+    fn breakpoints_and_stepping() {
+        let rom = Rom {
+            reg_a: 2,
+            reg_b: 0,
+            reg_c: 0,
+            program: vec![0, 1, 5, 4, 3, 0],
+        };
+        let mut emu = rom.boot();
+        emu.add_breakpoint(4);
+
+        assert_eq!(emu.resume().unwrap(), StopReason::Breakpoint(4));
+        assert_eq!(emu.machine().output, [1]);
+        assert_eq!(emu.step().unwrap().unwrap().to_string(), "jnz 0");
+        assert_eq!(emu.resume().unwrap(), StopReason::Breakpoint(4));
+        assert_eq!(emu.machine().output, [1, 0]);
+        assert_eq!(emu.resume().unwrap(), StopReason::Halted);
+        assert!(emu.step().unwrap().is_none());
+    }
+
+    #[test]
+    fn trace_records_state_before_each_step() {
+        let rom = Rom {
+            reg_a: 0,
+            reg_b: 29,
+            reg_c: 0,
+            program: vec![1, 7, 2, 6],
+        };
+        let mut emu = rom.boot();
+        emu.enable_trace();
+        emu.run().unwrap();
+
+        let trace = emu
+            .trace()
+            .iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            trace,
+            ["pc=00 bxl 7  A=0 B=29 C=0", "pc=02 bst C  A=0 B=26 C=0"]
+        );
     }
 
     #[test]
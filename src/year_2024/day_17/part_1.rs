@@ -1,7 +1,7 @@
 use itertools::Itertools;
 use macros::aoc_solver;
 
-use super::{parse_input, Emulator};
+use super::parse_input;
 
 use anyhow::Result;
 
@@ -9,10 +9,10 @@ use anyhow::Result;
 fn solve(input: &str) -> Result<String> {
     let rom = parse_input(input)?;
 
-    let mut emu = Emulator::from_rom(rom);
-    let output = emu.run()?;
+    let mut emu = rom.boot();
+    emu.run()?;
 
-    Ok(output.iter().join(","))
+    Ok(emu.machine().output.iter().join(","))
 }
 
 #[cfg(test)]
@@ -1,114 +1,107 @@
 use itertools::Itertools;
 use macros::aoc_solver;
 
-use super::parse_input;
+use super::{parse_input, Rom};
 
 use anyhow::{bail, Result};
 
 #[aoc_solver(2024, 17, 2, super::INPUT)]
 fn solve(input: &str) -> Result<String> {
     let rom = parse_input(input)?;
+    validate_program_structure(&rom.program)?;
 
-    let start_values = find_first_set_of_numbers(*rom.program.last().unwrap() as u128);
-    let candidates = iterate_set_of_numbers(&start_values, &rom.program[..&rom.program.len() - 1]);
-    let res = filter_candiate(&candidates, &rom.program)?;
+    let Some(res) = find_register_a(&rom, 0, rom.program.len())? else {
+        bail!("No value of register A makes the program output itself");
+    };
 
     Ok(res.to_string())
 }
 
-fn filter_candiate(candidates: &[u128], output: &[u8]) -> Result<u128> {
-    let output = output.iter().cloned().map(|v| v as u128).collect_vec();
-    for start_value in candidates.iter().sorted() {
-        if does_generate_output(*start_value, &output) {
-            return Ok(*start_value);
-        }
+// The search relies on the program being a single loop which outputs one value and shifts
+// register A by 3 bits per iteration, so every output depends only on the upper bits of A.
+fn validate_program_structure(program: &[u8]) -> Result<()> {
+    if !program.len().is_multiple_of(2) {
+        bail!("Program has an odd number of values: {}", program.len());
     }
 
-    bail!("no result found");
-}
-
-fn iterate_set_of_numbers(start_values: &[u128], remainding_outputs: &[u8]) -> Vec<u128> {
-    let target_outputs = remainding_outputs.iter().cloned().rev().collect_vec();
-    let mut potential_values = start_values.to_vec();
-    let mut next_values = Vec::new();
-
-    for &target_output in target_outputs.iter() {
-        let target_output = target_output as u128;
-
-        for value in potential_values.iter() {
-            let shifted_a = value << 3;
-            let next_step_values = vec![
-                shifted_a,
-                shifted_a + 1,
-                shifted_a + 2,
-                shifted_a + 3,
-                shifted_a + 4,
-                shifted_a + 5,
-                shifted_a + 6,
-                shifted_a + 7,
-            ];
-            for v in next_step_values {
-                let actual_output = compute_single_output_value(v);
-                if actual_output == target_output {
-                    next_values.push(v);
-                }
-            }
-        }
-
-        potential_values.clone_from(&next_values);
-        next_values.clear();
+    let instructions = program.chunks(2).map(|c| (c[0], c[1])).collect_vec();
+    if instructions.last() != Some(&(3, 0)) {
+        bail!("Program must end with a jump back to the start (3,0)");
     }
 
-    potential_values
-}
+    let jumps = instructions.iter().filter(|(inst, _)| *inst == 3).count();
+    if jumps != 1 {
+        bail!("Program must contain exactly one jump, found {}", jumps);
+    }
 
-fn find_first_set_of_numbers(last_value: u128) -> Vec<u128> {
-    let mut values = Vec::new();
-    let start = 0b0000000;
-    let end = 0b00000011_11111111;
+    let outputs = instructions.iter().filter(|(inst, _)| *inst == 5).count();
+    if outputs != 1 {
+        bail!(
+            "Program must output exactly one value per loop, found {}",
+            outputs
+        );
+    }
 
-    for a in start..=end {
-        let value = compute_single_output_value(a);
-        if value == last_value {
-            values.push(a);
-        }
+    let a_shifts = instructions
+        .iter()
+        .filter(|(inst, _)| *inst == 0)
+        .collect_vec();
+    if a_shifts != [&(0, 3)] {
+        bail!("Program must shift register A by 3 bits exactly once per loop (0,3)");
     }
 
-    values
+    Ok(())
 }
 
-fn compute_single_output_value(a: u128) -> u128 {
-    ((((a % 8) ^ 1) ^ (a / 2_u128.pow(((a % 8_u128) ^ 1) as u32))) ^ 4) % 8
-}
+// Determines A three bits at a time, starting with the bits producing the last output. Trying the
+// lowest bits first means the first match is the smallest value.
+fn find_register_a(rom: &Rom, prefix: u128, remaining: usize) -> Result<Option<u128>> {
+    if remaining == 0 {
+        return Ok(Some(prefix));
+    }
+
+    let expected = &rom.program[remaining - 1..];
+    for bits in 0..8 {
+        let reg_a = (prefix << 3) | bits;
+        let mut emu = Rom {
+            reg_a,
+            ..rom.clone()
+        }
+        .boot();
 
-fn does_generate_output(a: u128, values: &[u128]) -> bool {
-    let mut current_a = a;
-    for value in values {
-        let output = compute_single_output_value(current_a);
-        if output != *value {
-            return false;
+        emu.run()?;
+        if emu.machine().output != expected {
+            continue;
         }
 
-        current_a /= 8;
+        if let Some(res) = find_register_a(rom, reg_a, remaining - 1)? {
+            return Ok(Some(res));
+        }
     }
 
-    true
+    Ok(None)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::does_generate_output;
-
     #[test]
-    fn check_values() {
-        let values: Vec<u128> = vec![1, 3, 7, 4, 6, 4, 2, 3, 5];
-        let res = does_generate_output(30553366, &values);
-        assert!(res)
+    fn solve_example() {
+        let result = super::solve(include_str!("example_part_2.txt")).unwrap();
+        assert_eq!(result, "117440");
     }
 
     #[test]
-    fn solve_example() {
+    fn solve_input() {
         let result = super::solve(include_str!("input.txt")).unwrap();
         assert_eq!(result, "202367025818154");
     }
+
+    #[test]
+    fn reject_program_without_shift_by_3() {
+        let err = super::solve(include_str!("example.txt")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Program must shift register A by 3 bits exactly once per loop (0,3)"
+        );
+    }
 }
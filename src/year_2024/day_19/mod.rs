@@ -2,6 +2,8 @@ use anyhow::{bail, Result};
 use itertools::Itertools;
 use regex::Regex;
 
+use crate::common::parsing::split_blocks;
+
 mod part_1;
 mod part_2;
 
@@ -27,7 +29,7 @@ fn get_valid_designs(puzzle_input: &PuzzleInput) -> Result<Vec<String>> {
 }
 
 fn parse_input(input: &str) -> Result<PuzzleInput> {
-    let blocks = input.split("\r\n\r\n").collect_vec();
+    let blocks = split_blocks(input);
 
     if blocks.len() != 2 {
         bail!("Expected 2 blocks");
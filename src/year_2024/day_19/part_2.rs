@@ -1,8 +1,8 @@
-use std::collections::HashMap;
-
 use anyhow::Result;
 use macros::aoc_solver;
 
+use crate::common::memo::Memo;
+
 use super::{get_valid_designs, parse_input};
 
 #[aoc_solver(2024, 19, 2, super::INPUT)]
@@ -10,11 +10,12 @@ fn solve(input: &str) -> Result<String> {
     let input = parse_input(input)?;
     let designs = get_valid_designs(&input)?;
 
-    let mut solved = HashMap::new();
+    let mut memo = Memo::new();
     let sum: u64 = designs
         .iter()
-        .map(|d| get_permutation_count(d, &input.patterns, &mut solved))
+        .map(|d| get_permutation_count(d, &input.patterns, &mut memo))
         .sum();
+    memo.report_stats("Design arrangement cache");
 
     Ok(sum.to_string())
 }
@@ -22,28 +23,19 @@ fn solve(input: &str) -> Result<String> {
 fn get_permutation_count<'a>(
     design: &'a str,
     patterns: &[String],
-    solved: &mut HashMap<&'a str, u64>,
+    memo: &mut Memo<&'a str, u64>,
 ) -> u64 {
-    if let Some(count) = solved.get(design) {
-        return *count;
-    };
-
     if design.is_empty() {
         return 1;
     }
 
-    let mut sum = 0;
-    for pattern in patterns
-        .iter()
-        .filter(|pattern| design.starts_with(*pattern))
-    {
-        let simplified_desing = &design[pattern.len()..];
-        let count = get_permutation_count(simplified_desing, patterns, solved);
-        solved.insert(simplified_desing, count);
-        sum += count;
-    }
-
-    sum
+    memo.get_or_compute(design, |memo| {
+        patterns
+            .iter()
+            .filter(|pattern| design.starts_with(*pattern))
+            .map(|pattern| get_permutation_count(&design[pattern.len()..], patterns, memo))
+            .sum()
+    })
 }
 
 #[cfg(test)]
@@ -4,15 +4,12 @@ use macros::aoc_solver;
 
 use anyhow::{anyhow, Result};
 
-static INPUT: &str = include_str!("input.txt");
+use crate::common::intervals::IntervalSet;
 
-struct Range {
-    start: u64,
-    end: u64,
-}
+static INPUT: &str = include_str!("input.txt");
 
 struct IngredientData {
-    fresh_ingredients: Vec<Range>,
+    fresh_ingredients: IntervalSet<u64>,
     ingredients: HashSet<u64>,
 }
 
@@ -23,7 +20,7 @@ fn solve_part_1(input: &str) -> Result<String> {
     let count = data
         .ingredients
         .into_iter()
-        .filter(|id| is_fresh(*id, &data.fresh_ingredients))
+        .filter(|id| data.fresh_ingredients.contains(*id))
         .count();
 
     return Ok(count.to_string());
@@ -32,44 +29,12 @@ fn solve_part_1(input: &str) -> Result<String> {
 #[aoc_solver(2025, 5, 2, INPUT)]
 fn solve_part_2(input: &str) -> Result<String> {
     let data = parse_input(input)?;
-    let count = count_ranges(&data.fresh_ingredients);
+    let count = data.fresh_ingredients.covered_length();
     Ok(count.to_string())
 }
 
-fn count_ranges(ranges: &[Range]) -> u64 {
-    let mut count = 0;
-    let mut highest_id = 0;
-
-    for range in ranges {
-        if range.end <= highest_id {
-            continue;
-        }
-
-        let start = range.start.max(highest_id + 1);
-        count += range.end - start + 1;
-
-        highest_id = range.end;
-    }
-
-    return count;
-}
-
-fn is_fresh(ingredient: u64, fresh_ingredients: &[Range]) -> bool {
-    for range in fresh_ingredients {
-        if ingredient < range.start {
-            return false;
-        }
-
-        if ingredient <= range.end {
-            return true;
-        }
-    }
-
-    return false;
-}
-
 fn parse_input(input: &str) -> Result<IngredientData> {
-    let mut fresh_ingredients = Vec::new();
+    let mut fresh_ingredients = IntervalSet::new();
     let mut ingredients = HashSet::new();
 
     let mut lines = input.lines().into_iter();
@@ -90,14 +55,9 @@ fn parse_input(input: &str) -> Result<IngredientData> {
             .ok_or(anyhow!("Could not parse range: {}", line))?
             .parse::<u64>()?;
 
-        fresh_ingredients.push(Range {
-            start: lower,
-            end: upper,
-        });
+        fresh_ingredients.insert(lower..upper + 1);
     }
 
-    fresh_ingredients.sort_by(|a, b| a.start.cmp(&b.start));
-
     for line in lines {
         let id = line.parse::<u64>()?;
         ingredients.insert(id);
@@ -1,11 +1,12 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 
 use macros::aoc_solver;
 
 use anyhow::{anyhow, Result};
 
 use crate::common::{
-    math_2d::{Grid, UPoint},
+    math_2d::{Grid, UPoint, Vec2},
+    memo::Memo,
     parsing,
 };
 
@@ -37,10 +38,10 @@ fn solve_part_1(input: &str) -> Result<String> {
 
             hit_splitters.insert(splitter_pos);
 
-            if let Some(left_beam) = splitter_pos.checked_sub(UPoint::new(1, 0)) {
+            if let Some(left_beam) = splitter_pos.checked_sub(Vec2::new(1, 0)) {
                 next_beam_set.insert(left_beam);
             }
-            if let Some(right_beam) = splitter_pos.checked_add(UPoint::new(1, 0)) {
+            if let Some(right_beam) = splitter_pos.checked_add(Vec2::new(1, 0)) {
                 next_beam_set.insert(right_beam);
             }
         }
@@ -62,30 +63,24 @@ fn solve_part_2(input: &str) -> Result<String> {
         .find_value(Tile::Start)
         .ok_or(anyhow!("Input does not contain the starting position"))?;
 
-    let mut cache = HashMap::new();
-    let count = count_beam_paths(&grid, start_pos, &mut cache);
+    let mut memo = Memo::new();
+    let count = count_beam_paths(&grid, start_pos, &mut memo);
+    memo.report_stats("Beam path cache");
+
     Ok(count.to_string())
 }
 
-fn count_beam_paths(grid: &Grid<Tile>, start: UPoint, cache: &mut HashMap<UPoint, usize>) -> usize {
-    if let Some(count) = cache.get(&start) {
-        return *count;
-    }
-
-    let Some(split_pos) = find_splitter_pos(grid, start) else {
-        return 1;
-    };
-
-    let left_beam = split_pos.checked_sub(UPoint::new(1, 0)).unwrap();
-    let right_beam = split_pos.checked_add(UPoint::new(1, 0)).unwrap();
-
-    let left_branch = count_beam_paths(grid, left_beam, cache);
-    let right_branch = count_beam_paths(grid, right_beam, cache);
+fn count_beam_paths(grid: &Grid<Tile>, start: UPoint, memo: &mut Memo<UPoint, usize>) -> usize {
+    memo.get_or_compute(start, |memo| {
+        let Some(split_pos) = find_splitter_pos(grid, start) else {
+            return 1;
+        };
 
-    cache.insert(left_beam, left_branch);
-    cache.insert(right_beam, right_branch);
+        let left_beam = split_pos.checked_sub(Vec2::new(1, 0)).unwrap();
+        let right_beam = split_pos.checked_add(Vec2::new(1, 0)).unwrap();
 
-    left_branch + right_branch
+        count_beam_paths(grid, left_beam, memo) + count_beam_paths(grid, right_beam, memo)
+    })
 }
 
 fn find_splitter_pos(grid: &Grid<Tile>, beam: UPoint) -> Option<UPoint> {
@@ -2,7 +2,9 @@ use std::collections::HashSet;
 
 use macros::aoc_solver;
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, ensure, Context, Result};
+
+use crate::common::linalg::solve_linear_system;
 
 static INPUT: &str = include_str!("input.txt");
 
@@ -12,6 +14,7 @@ type BitPattern = u64;
 struct MachineData {
     light_pattern: BitPattern,
     wirings: Vec<BitPattern>,
+    wiring_lights: Vec<HashSet<LightIndex>>,
     joltages: Vec<u32>,
 }
 
@@ -22,6 +25,58 @@ fn solve_part_1(input: &str) -> Result<String> {
     return Ok(result.to_string());
 }
 
+#[aoc_solver(2025, 10, 2, INPUT)]
+fn solve_part_2(input: &str) -> Result<String> {
+    let machines = parse_input(input)?;
+    let mut result = 0;
+    for (idx, machine) in machines.iter().enumerate() {
+        result += get_required_joltage_presses(machine)
+            .with_context(|| format!("Failed to configure joltages of machine {}", idx + 1))?;
+    }
+
+    Ok(result.to_string())
+}
+
+fn get_required_joltage_presses(machine: &MachineData) -> Result<i64> {
+    let coefficients = (0..machine.joltages.len())
+        .map(|counter| {
+            machine
+                .wiring_lights
+                .iter()
+                .map(|lights| i64::from(lights.contains(&(counter as LightIndex))))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let joltages = machine
+        .joltages
+        .iter()
+        .map(|&j| i64::from(j))
+        .collect::<Vec<_>>();
+
+    let upper_bounds = machine
+        .wiring_lights
+        .iter()
+        .map(|lights| {
+            lights
+                .iter()
+                .filter_map(|&light| machine.joltages.get(light as usize))
+                .map(|&j| u64::from(j))
+                .min()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+
+    let space = solve_linear_system(&coefficients, &joltages)?
+        .ok_or_else(|| anyhow!("The buttons cannot reach the joltage levels"))?;
+    let (presses, _) = space
+        .minimize(&vec![1; machine.wiring_lights.len()], &upper_bounds)?
+        .ok_or_else(|| {
+            anyhow!("No non-negative number of button presses reaches the joltage levels")
+        })?;
+
+    Ok(presses)
+}
+
 fn get_required_presses(machine: &MachineData) -> u64 {
     let mut start_patterns = HashSet::from_iter([0]);
     button_sequence_bfs(&mut start_patterns, &machine, 0)
@@ -69,10 +124,24 @@ fn parse_input(input: &str) -> Result<Vec<MachineData>> {
         let parts = line.split(" ").collect::<Vec<_>>();
 
         let (light_pattern, light_count) = parse_lights(&parts)?;
+        let wiring_lights = parse_wirings(&parts)?;
+        let joltages = parse_joltages(&parts)?;
+        ensure!(
+            joltages.len() == light_count,
+            "Expected {} joltages but found {} in '{}'",
+            light_count,
+            joltages.len(),
+            line
+        );
+
         let m = MachineData {
             light_pattern,
-            wirings: parse_wirings(&parts, light_count)?,
-            joltages: parse_voltages(&parts)?,
+            wirings: wiring_lights
+                .iter()
+                .map(|lights| create_bit_pattern(lights, light_count))
+                .collect(),
+            wiring_lights,
+            joltages,
         };
         machines.push(m);
     }
@@ -80,11 +149,25 @@ fn parse_input(input: &str) -> Result<Vec<MachineData>> {
     Ok(machines)
 }
 
-fn parse_voltages(split_line: &[&str]) -> Result<Vec<u32>> {
-    Ok(Vec::new())
+fn parse_joltages(split_line: &[&str]) -> Result<Vec<u32>> {
+    let schema = split_line
+        .last()
+        .ok_or_else(|| anyhow!("Failed to parse joltage requirements"))?;
+    let values = schema
+        .strip_prefix('{')
+        .and_then(|s| s.strip_suffix('}'))
+        .ok_or_else(|| anyhow!("Invalid joltage requirements {}", schema))?;
+
+    values
+        .split(',')
+        .map(|v| {
+            v.parse::<u32>()
+                .with_context(|| format!("Failed to parse the joltage requirements {}", schema))
+        })
+        .collect()
 }
 
-fn parse_wirings(split_line: &[&str], light_count: usize) -> Result<Vec<BitPattern>> {
+fn parse_wirings(split_line: &[&str]) -> Result<Vec<HashSet<LightIndex>>> {
     let patterns = split_line
         .iter()
         .skip(1)
@@ -100,7 +183,6 @@ fn parse_wirings(split_line: &[&str], light_count: usize) -> Result<Vec<BitPatte
                 })
                 .collect::<Result<HashSet<_>>>()
         })
-        .map(|res| res.map(|indeces| create_bit_pattern(&indeces, light_count)))
         .collect::<Result<Vec<_>>>()?;
     Ok(patterns)
 }
@@ -141,6 +223,18 @@ mod tests {
         assert_eq!(result, "494");
     }
 
+    #[test]
+    fn solve_example_part_2() {
+        let result = super::solve_part_2(include_str!("example.txt")).unwrap();
+        assert_eq!(result, "33");
+    }
+
+    #[test]
+    fn solve_part_2() {
+        let result = super::solve_part_2(super::INPUT).unwrap();
+        assert_eq!(result, "19235");
+    }
+
     #[test]
     fn create_bitpattern_for_lighs() {
         let res = super::create_bit_pattern(&HashSet::from_iter([1, 2]), 4);